@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+
+use git2::Commit;
+
+use super::errors::*;
+use super::Context;
+use dates;
+use history::{CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+pub static WEEKDAYS: &'static [&'static str] = &[
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+const FRIDAY: usize = 4;
+
+/// Commit counts bucketed by day of week (Monday first) and hour of day, in the timezone of each
+/// commit's author. Counts are fractional for teams, as people in several teams only give each of
+/// them their share of a commit.
+#[derive(Debug, Default)]
+pub struct Heatmap {
+    matrix: [[f64; 24]; 7],
+}
+
+impl Heatmap {
+    fn add_commit(&mut self, commit: &Commit, share: f64) {
+        let time = commit.author().when();
+        let weekday = dates::weekday(dates::local_day(&time));
+        let hour = dates::local_hour(&time);
+        self.matrix[weekday][hour] += share;
+    }
+
+    pub fn matrix(&self) -> &[[f64; 24]; 7] {
+        &self.matrix
+    }
+
+    pub fn get(&self, weekday: usize, hour: usize) -> f64 {
+        self.matrix[weekday][hour]
+    }
+
+    pub fn total(&self) -> f64 {
+        self.matrix.iter().map(|hours| hours.iter().sum::<f64>()).sum()
+    }
+
+    pub fn max(&self) -> f64 {
+        self.matrix
+            .iter()
+            .flat_map(|hours| hours.iter())
+            .fold(0.0, |max, commits| max.max(*commits))
+    }
+
+    pub fn on_weekday(&self, weekday: usize) -> f64 {
+        self.matrix[weekday].iter().sum()
+    }
+
+    pub fn at_hour(&self, hour: usize) -> f64 {
+        self.matrix.iter().map(|hours| hours[hour]).sum()
+    }
+
+    /// Commits made between midnight and 06:00.
+    pub fn at_night(&self) -> f64 {
+        (0..6).map(|hour| self.at_hour(hour)).sum()
+    }
+
+    pub fn busiest_weekday(&self) -> Option<usize> {
+        busiest((0..7).map(|weekday| self.on_weekday(weekday)))
+    }
+
+    pub fn busiest_hour(&self) -> Option<usize> {
+        busiest((0..24).map(|hour| self.at_hour(hour)))
+    }
+}
+
+fn busiest<I>(counts: I) -> Option<usize>
+where
+    I: Iterator<Item = f64>,
+{
+    counts
+        .enumerate()
+        .filter(|&(_, count)| count > 0.0)
+        .fold(None, |best: Option<(usize, f64)>, (index, count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((index, count)),
+        })
+        .map(|(index, _)| index)
+}
+
+#[derive(Debug)]
+pub struct ActivityStatistics<'context> {
+    pub repository: Heatmap,
+    pub combined_tracking: CombinedTracking<'context, Heatmap>,
+}
+
+impl<'context> ActivityStatistics<'context> {
+    pub fn people_toplist(&self) -> Vec<(&Person, &Heatmap)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, heatmap)| (*person, heatmap))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.total().partial_cmp(&a.1.total()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, &Heatmap)> {
+        let mut toplist: Vec<_> = self.combined_tracking.team_iter().collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.total().partial_cmp(&a.1.total()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    /// The person with the most commits made between midnight and 06:00.
+    pub fn night_owl(&self) -> Option<(&Person, f64)> {
+        self.people_toplist()
+            .into_iter()
+            .map(|(person, heatmap)| (person, heatmap.at_night()))
+            .filter(|&(_, commits)| commits > 0.0)
+            .fold(None, |best: Option<(&Person, f64)>, (person, commits)| match best {
+                Some((_, best_commits)) if best_commits >= commits => best,
+                _ => Some((person, commits)),
+            })
+    }
+
+    /// The team with the most commits made on Fridays.
+    pub fn friday_team(&self) -> Option<(Option<&str>, f64)> {
+        self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, heatmap)| (team_name, heatmap.on_weekday(FRIDAY)))
+            .filter(|&(_, commits)| commits > 0.0)
+            .fold(None, |best: Option<(Option<&str>, f64)>, (team_name, commits)| match best {
+                Some((_, best_commits)) if best_commits >= commits => best,
+                _ => Some((team_name, commits)),
+            })
+    }
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<ActivityStatistics<'context>> {
+    let people_db = context.people_db();
+
+    let mut repository = Heatmap::default();
+    let mut tracking: CombinedTracking<Heatmap> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        let person = people_db.find_by_signature(commit.author())?;
+        repository.add_commit(&commit, 1.0);
+        tracking.track_person_weighted(
+            person,
+            |heatmap, weight| heatmap.add_commit(&commit, weight),
+        );
+    }
+
+    Ok(ActivityStatistics {
+        repository: repository,
+        combined_tracking: tracking,
+    })
+}
@@ -0,0 +1,267 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use git2::{Commit, Delta, Repository};
+
+use super::errors::*;
+use super::Context;
+use count;
+use history::{self, CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+const BIGGEST_COMMITS: usize = 10;
+
+/// Changes made by a person or team. Teams only get their share of the changes of people that
+/// are in several teams, so their counts can be fractional.
+#[derive(Debug, Default)]
+pub struct ChurnScore {
+    commits: f64,
+    insertions: f64,
+    deletions: f64,
+    files: HashSet<PathBuf>,
+}
+
+impl ChurnScore {
+    fn add_commit(&mut self, change: &CommitChange, share: f64) {
+        self.commits += share;
+        self.insertions += change.insertions as f64 * share;
+        self.deletions += change.deletions as f64 * share;
+        self.files.extend(change.files.iter().cloned());
+    }
+
+    fn compute(&self) -> ComputedChurn {
+        ComputedChurn {
+            commits: self.commits,
+            insertions: self.insertions,
+            deletions: self.deletions,
+            net_lines: self.insertions - self.deletions,
+            files_touched: self.files.len(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedChurn {
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub insertions: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub deletions: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub net_lines: f64,
+    pub files_touched: usize,
+}
+
+impl ComputedChurn {
+    pub fn changed_lines(&self) -> f64 {
+        self.insertions + self.deletions
+    }
+}
+
+#[derive(Debug)]
+pub struct BigCommit<'context> {
+    pub sha: String,
+    pub summary: String,
+    pub person: &'context Person,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+impl<'context> BigCommit<'context> {
+    pub fn changed_lines(&self) -> u64 {
+        self.insertions + self.deletions
+    }
+}
+
+#[derive(Debug)]
+pub struct ChurnStatistics<'context> {
+    pub repository: ChurnScore,
+    pub combined_tracking: CombinedTracking<'context, ChurnScore>,
+    pub biggest_commits: Vec<BigCommit<'context>>,
+}
+
+impl<'context> ChurnStatistics<'context> {
+    pub fn repository_churn(&self) -> ComputedChurn {
+        self.repository.compute()
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedChurn)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, score)| (*person, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.changed_lines().partial_cmp(&a.1.changed_lines()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedChurn)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, score)| (team_name, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.changed_lines().partial_cmp(&a.1.changed_lines()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    /// People sorted by how many lines they have added in total, after subtracting the lines they
+    /// have removed.
+    pub fn net_lines_leaderboard(&self) -> Vec<(&Person, ComputedChurn)> {
+        let mut leaderboard = self.people_toplist();
+        // Note: Reverse sort
+        leaderboard.sort_by(|a, b| {
+            b.1.net_lines.partial_cmp(&a.1.net_lines).unwrap_or(Ordering::Equal)
+        });
+        leaderboard
+    }
+}
+
+/// What a single commit changed compared to its first parent.
+struct CommitChange {
+    insertions: u64,
+    deletions: u64,
+    files: Vec<PathBuf>,
+}
+
+impl CommitChange {
+    fn from_commit(repo: &Repository, commit: &Commit) -> Result<CommitChange> {
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+        let tree = commit.tree()?;
+
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let stats = diff.stats()?;
+
+        // Binary files are skipped, just like when calculating ownership. They never count towards
+        // inserted or deleted lines, so only the list of touched files needs to be filtered.
+        let files = diff.deltas()
+            .filter_map(|delta| {
+                let file = match delta.status() {
+                    Delta::Deleted => delta.old_file(),
+                    _ => delta.new_file(),
+                };
+                let is_binary = repo.find_blob(file.id())
+                    .map(|blob| blob.is_binary())
+                    .unwrap_or(false);
+                if is_binary {
+                    None
+                } else {
+                    file.path().map(|path| path.to_path_buf())
+                }
+            })
+            .collect();
+
+        Ok(CommitChange {
+            insertions: stats.insertions() as u64,
+            deletions: stats.deletions() as u64,
+            files: files,
+        })
+    }
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+    include_merges: bool,
+) -> Result<ChurnStatistics<'context>> {
+    let people_db = context.people_db();
+    let repo = context.repo();
+
+    let mut repository = ChurnScore::default();
+    let mut tracking: CombinedTracking<ChurnScore> = CombinedTracking::new();
+    let mut biggest_commits: Vec<BigCommit> = Vec::new();
+
+    for commit in CommitWalker::new(repo, selection)? {
+        if history::is_merge(&commit) && !include_merges {
+            continue;
+        }
+
+        let person = people_db.find_by_signature(commit.author())?;
+        let change = CommitChange::from_commit(repo, &commit)?;
+
+        repository.add_commit(&change, 1.0);
+        tracking.track_person_weighted(
+            person,
+            |score, weight| score.add_commit(&change, weight),
+        );
+
+        biggest_commits.push(BigCommit {
+            sha: commit.id().to_string(),
+            summary: commit.summary().unwrap_or("").to_owned(),
+            person: person,
+            insertions: change.insertions,
+            deletions: change.deletions,
+        });
+        if biggest_commits.len() > BIGGEST_COMMITS * 10 {
+            keep_biggest(&mut biggest_commits);
+        }
+    }
+    keep_biggest(&mut biggest_commits);
+
+    Ok(ChurnStatistics {
+        repository: repository,
+        combined_tracking: tracking,
+        biggest_commits: biggest_commits,
+    })
+}
+
+fn keep_biggest(commits: &mut Vec<BigCommit>) {
+    commits.sort_by(|a, b| b.changed_lines().cmp(&a.changed_lines())); // Note: Reverse sort
+    commits.truncate(BIGGEST_COMMITS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    #[test]
+    fn it_splits_changes_by_share() {
+        let change = CommitChange {
+            insertions: 10,
+            deletions: 4,
+            files: vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")],
+        };
+
+        let mut score = ChurnScore::default();
+        score.add_commit(&change, 0.5);
+        score.add_commit(&change, 1.0);
+
+        let computed = score.compute();
+        assert_eq!(computed.commits, 1.5);
+        assert_eq!(computed.insertions, 15.0);
+        assert_eq!(computed.deletions, 6.0);
+        assert_eq!(computed.net_lines, 9.0);
+        assert_eq!(computed.files_touched, 2);
+    }
+
+    #[test]
+    fn it_compares_merges_with_their_first_parent() {
+        let mut test_repo = TestRepo::new();
+        let base = test_repo.commit(&[], &[("a.txt", "1\n")], "Base");
+        let side = test_repo.commit(&[base], &[("a.txt", "1\n2\n3\n")], "Side");
+        let main = test_repo.commit(&[base], &[("a.txt", "1\n"), ("b.txt", "b\n")], "Main");
+        let merge = test_repo.commit(
+            &[main, side],
+            &[("a.txt", "1\n2\n3\n"), ("b.txt", "b\n")],
+            "Merge",
+        );
+
+        let repo = &test_repo.repo;
+        let change = CommitChange::from_commit(repo, &repo.find_commit(merge).unwrap()).unwrap();
+        // Only what the side branch brought in, not the changes already made on main.
+        assert_eq!(change.insertions, 2);
+        assert_eq!(change.deletions, 0);
+        assert_eq!(change.files, vec![PathBuf::from("a.txt")]);
+    }
+}
@@ -0,0 +1,140 @@
+use git2::Commit;
+
+use person::{Email, PeopleDatabase, Person};
+
+/// How work on a commit with co-authors is credited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CreditMode {
+    /// Only the author of the commit gets credit, like git itself sees it.
+    Primary,
+    /// The author and every co-author get an equal share of the credit.
+    Split,
+    /// The author and every co-author get full credit.
+    Duplicate,
+}
+
+impl Default for CreditMode {
+    fn default() -> CreditMode {
+        CreditMode::Primary
+    }
+}
+
+impl CreditMode {
+    pub fn is_primary(&self) -> bool {
+        *self == CreditMode::Primary
+    }
+}
+
+/// A name and email from a `Co-authored-by:` trailer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+}
+
+/// Finds the `Co-authored-by: Name <email>` trailers of a commit message.
+pub fn parse_trailers(message: &str) -> Vec<Identity> {
+    message.lines().filter_map(parse_trailer).collect()
+}
+
+fn parse_trailer(line: &str) -> Option<Identity> {
+    let separator = match line.find(':') {
+        Some(separator) => separator,
+        None => return None,
+    };
+    if !line[0..separator].trim().eq_ignore_ascii_case("co-authored-by") {
+        return None;
+    }
+
+    let value = line[separator + 1..].trim();
+    match (value.find('<'), value.rfind('>')) {
+        (Some(start), Some(end)) if start < end => {
+            Some(Identity {
+                name: value[0..start].trim().to_owned(),
+                email: value[start + 1..end].trim().to_owned(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The co-authors of a commit that are in the people database.
+///
+/// Unknown co-authors are ignored, since older configurations did not collect them.
+pub fn co_authors<'people>(
+    people_db: &'people PeopleDatabase,
+    commit: &Commit,
+) -> Vec<&'people Person> {
+    let mut people: Vec<&Person> = Vec::new();
+    for identity in parse_trailers(commit.message().unwrap_or("")) {
+        if let Ok(person) = people_db.find_by_email(&Email::from(identity.email)) {
+            if !people.contains(&person) {
+                people.push(person);
+            }
+        }
+    }
+    people
+}
+
+/// The share of the credit the author and every co-author gets, author first.
+pub fn credit<'people>(
+    author: &'people Person,
+    co_authors: &[&'people Person],
+    mode: CreditMode,
+) -> Vec<(&'people Person, f64)> {
+    let mut contributors = vec![author];
+    if mode != CreditMode::Primary {
+        contributors.extend(co_authors.iter().filter(|person| **person != author));
+    }
+
+    let share = match mode {
+        CreditMode::Split => 1.0 / contributors.len() as f64,
+        CreditMode::Primary | CreditMode::Duplicate => 1.0,
+    };
+    contributors.into_iter().map(|person| (person, share)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_co_author_trailers() {
+        let message = "Pair on the parser\n\nSome details: none\n\n\
+                       Co-authored-by: Jane Doe <jane@example.com>\n\
+                       co-authored-by:John Doe <john@example.com >\n\
+                       Co-authored-by: Nobody\n";
+
+        assert_eq!(
+            parse_trailers(message),
+            vec![
+                Identity {
+                    name: String::from("Jane Doe"),
+                    email: String::from("jane@example.com"),
+                },
+                Identity {
+                    name: String::from("John Doe"),
+                    email: String::from("john@example.com"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_credits_contributors_by_mode() {
+        let jane = Person::new("Jane");
+        let john = Person::new("John");
+        let co_authors = vec![&john, &jane];
+
+        assert_eq!(credit(&jane, &co_authors, CreditMode::Primary), vec![(&jane, 1.0)]);
+        assert_eq!(
+            credit(&jane, &co_authors, CreditMode::Duplicate),
+            vec![(&jane, 1.0), (&john, 1.0)]
+        );
+        assert_eq!(
+            credit(&jane, &co_authors, CreditMode::Split),
+            vec![(&jane, 0.5), (&john, 0.5)]
+        );
+    }
+}
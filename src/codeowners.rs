@@ -0,0 +1,546 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+use super::errors::*;
+use super::Context;
+use ownership::FileOwnership;
+use person::Person;
+
+/// Code hosting services disagree slightly on where the file lives and what it may contain.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum Style {
+    GitHub,
+    GitLab,
+}
+
+impl Style {
+    pub fn from_name(name: &str) -> Result<Style> {
+        match name {
+            "github" => Ok(Style::GitHub),
+            "gitlab" => Ok(Style::GitLab),
+            other => bail!("Not a valid CODEOWNERS style: {}", other),
+        }
+    }
+
+    /// Places the file is looked for, relative to the root of the repository. The first one is
+    /// where new files are written.
+    fn candidate_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Style::GitHub => &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"],
+            Style::GitLab => &[".gitlab/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"],
+        }
+    }
+}
+
+/// Finds the CODEOWNERS file of the repository, or where it should be written if there is none.
+pub fn file_path(context: &Context, style: Style) -> Result<PathBuf> {
+    let workdir = match context.repo().workdir() {
+        Some(workdir) => workdir,
+        None => bail!("CODEOWNERS files need a repository with a working directory"),
+    };
+
+    let candidates = style.candidate_paths();
+    Ok(
+        candidates
+            .iter()
+            .map(|path| workdir.join(path))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| workdir.join(candidates[0])),
+    )
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Rule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodeOwners {
+    pub style: Style,
+    pub rules: Vec<Rule>,
+    /// Teams that own lines but were left out, because they have no handle to put in the file.
+    #[serde(skip_serializing)]
+    pub skipped_teams: Vec<String>,
+}
+
+impl CodeOwners {
+    pub fn to_file_contents(&self) -> String {
+        let mut contents = String::from(
+            "# Generated by git-trivia from line ownership. Later rules take precedence.\n",
+        );
+        if self.style == Style::GitLab {
+            contents.push_str("[Ownership]\n");
+        }
+        for rule in &self.rules {
+            contents.push_str(&rule.pattern);
+            for owner in &rule.owners {
+                contents.push(' ');
+                contents.push_str(owner);
+            }
+            contents.push('\n');
+        }
+        contents
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub style: Style,
+    /// Share of the lines in a directory someone must own to be listed as an owner.
+    pub threshold: f64,
+    pub max_owners: usize,
+    pub depth: usize,
+    /// Assign directories to teams instead of people.
+    pub teams: bool,
+}
+
+/// The name to put in CODEOWNERS for a person: their handle if they have one, otherwise an email.
+pub fn person_owner(person: &Person) -> String {
+    match person.handle() {
+        Some(handle) => with_at_sign(handle),
+        None => {
+            let mut emails: Vec<&str> = person.emails().iter().map(|email| &**email).collect();
+            emails.sort();
+            emails.first().map(|email| email.to_string()).unwrap_or_else(
+                || person.name().to_owned(),
+            )
+        }
+    }
+}
+
+/// The name to put in CODEOWNERS for a team, if it has a handle configured.
+pub fn team_owner(context: &Context, team_name: &str) -> Option<String> {
+    context.team(team_name).and_then(|team| {
+        team.handle.as_ref().map(|handle| with_at_sign(handle))
+    })
+}
+
+pub fn with_at_sign(handle: &str) -> String {
+    if handle.starts_with('@') {
+        handle.to_owned()
+    } else {
+        format!("@{}", handle)
+    }
+}
+
+/// Generates one rule for the root of the repository, and one for every directory down to the
+/// given depth whose owners differ from the rule that would otherwise apply to it.
+pub fn generate(context: &Context, files: &[FileOwnership], options: &Options) -> CodeOwners {
+    let mut skipped_teams = BTreeSet::new();
+    let rules = if options.teams {
+        let directories = directory_lines(files, options.depth, |file| {
+            file.lines_per_team()
+                .into_iter()
+                .filter_map(|(team_name, lines)| {
+                    let team_name = match team_name {
+                        Some(team_name) => team_name,
+                        None => return None,
+                    };
+                    let owner = team_owner(context, team_name);
+                    if owner.is_none() {
+                        skipped_teams.insert(team_name.to_owned());
+                    }
+                    owner.map(|owner| (owner, lines))
+                })
+                .collect()
+        });
+        rules_for_directories(directories, options)
+    } else {
+        let directories = directory_lines(files, options.depth, |file| {
+            file.lines_per_person()
+                .into_iter()
+                .map(|(person, lines)| (person_owner(person), lines as f64))
+                .collect()
+        });
+        rules_for_directories(directories, options)
+    };
+
+    CodeOwners {
+        style: options.style,
+        rules: rules,
+        skipped_teams: skipped_teams.into_iter().collect(),
+    }
+}
+
+/// Sums up lines per owner for every directory, including all files below it.
+fn directory_lines<F>(
+    files: &[FileOwnership],
+    depth: usize,
+    mut owners_of_file: F,
+) -> BTreeMap<String, HashMap<String, f64>>
+where
+    F: FnMut(&FileOwnership) -> Vec<(String, f64)>,
+{
+    let mut directories: BTreeMap<String, HashMap<String, f64>> = BTreeMap::new();
+
+    for file in files {
+        let owners = owners_of_file(file);
+        for directory in ancestors(&file.path, depth) {
+            let lines = directories.entry(directory).or_insert_with(HashMap::new);
+            for &(ref owner, owned) in &owners {
+                *lines.entry(owner.clone()).or_insert(0.0) += owned;
+            }
+        }
+    }
+
+    directories
+}
+
+/// The directories containing the path, from the root ("") and down at most `depth` levels.
+fn ancestors(path: &Path, depth: usize) -> Vec<String> {
+    let mut directories = vec![String::new()];
+    if let Some(parent) = path.parent() {
+        let mut current = String::new();
+        for component in parent.components().take(depth) {
+            if !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(&component.as_os_str().to_string_lossy());
+            directories.push(current.clone());
+        }
+    }
+    directories
+}
+
+fn rules_for_directories(
+    directories: BTreeMap<String, HashMap<String, f64>>,
+    options: &Options,
+) -> Vec<Rule> {
+    let mut rules: Vec<Rule> = Vec::new();
+    // Directories are sorted, so a directory is always seen before the directories inside it.
+    let mut effective: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (directory, lines) in directories {
+        let owners = top_owners(&lines, options.threshold, options.max_owners);
+        let inherited = parent_directory(&directory).and_then(|parent| {
+            effective.get(&parent).cloned()
+        });
+
+        if !owners.is_empty() && inherited.as_ref() != Some(&owners) {
+            rules.push(Rule {
+                pattern: if directory.is_empty() {
+                    String::from("*")
+                } else {
+                    format!("/{}/", directory)
+                },
+                owners: owners.clone(),
+            });
+        }
+
+        let owners = if owners.is_empty() {
+            inherited.unwrap_or_default()
+        } else {
+            owners
+        };
+        effective.insert(directory, owners);
+    }
+
+    rules
+}
+
+fn parent_directory(directory: &str) -> Option<String> {
+    if directory.is_empty() {
+        None
+    } else {
+        match directory.rfind('/') {
+            Some(index) => Some(directory[0..index].to_owned()),
+            None => Some(String::new()),
+        }
+    }
+}
+
+/// Owners holding at least `threshold` of the lines, biggest first, at most `max_owners` of them.
+fn top_owners<K>(lines: &HashMap<K, f64>, threshold: f64, max_owners: usize) -> Vec<K>
+where
+    K: Hash + Eq + Ord + Clone,
+{
+    let total: f64 = lines.values().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut owners: Vec<(&K, f64)> = lines
+        .iter()
+        .map(|(owner, &owned)| (owner, owned))
+        .filter(|&(_, owned)| owned / total >= threshold)
+        .collect();
+    owners.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1).unwrap_or(::std::cmp::Ordering::Equal).then_with(
+            || a.0.cmp(b.0),
+        )
+    });
+
+    owners
+        .into_iter()
+        .take(max_owners)
+        .map(|(owner, _)| owner.clone())
+        .collect()
+}
+
+/// Parses the rules of a CODEOWNERS file, together with the line number they are on. Comments and
+/// GitLab section headers are skipped.
+pub fn parse(contents: &str) -> Vec<(usize, Rule)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line_without_comment = match line.find(" #") {
+                Some(position) => &line[0..position],
+                None => line,
+            };
+            let mut words = line_without_comment.split_whitespace();
+            match words.next() {
+                Some(pattern) if is_rule_pattern(pattern) => {
+                    Some((
+                        index + 1,
+                        Rule {
+                            pattern: pattern.to_owned(),
+                            owners: words.map(String::from).collect(),
+                        },
+                    ))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn is_rule_pattern(word: &str) -> bool {
+    !(word.starts_with('#') || word.starts_with('[') || word.starts_with("^["))
+}
+
+impl Rule {
+    /// Checks if the rule applies to a file, using the same rules as .gitignore files: patterns
+    /// without a slash match anywhere, other patterns are relative to the root of the repository,
+    /// and patterns matching a directory match everything inside it.
+    pub fn matches(&self, path: &str) -> bool {
+        let directory_only = self.pattern.ends_with('/');
+        let pattern = self.pattern.trim_right_matches('/');
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_left_matches('/');
+
+        if pattern.is_empty() {
+            return true;
+        }
+
+        let components: Vec<&str> = path.split('/').collect();
+        let last = components.len() - 1;
+
+        if anchored {
+            (0..components.len()).any(|index| {
+                (index < last || !directory_only) &&
+                    glob_matches(pattern.as_bytes(), components[0..index + 1].join("/").as_bytes())
+            })
+        } else {
+            components.iter().enumerate().any(|(index, component)| {
+                (index < last || !directory_only) &&
+                    glob_matches(pattern.as_bytes(), component.as_bytes())
+            })
+        }
+    }
+}
+
+/// Matches a glob where "*" and "?" stay within a directory while "**" crosses directories.
+fn glob_matches(pattern: &[u8], path: &[u8]) -> bool {
+    if pattern.is_empty() {
+        return path.is_empty();
+    }
+
+    if pattern.starts_with(b"**/") {
+        // Matches zero or more whole directories.
+        let rest = &pattern[3..];
+        return glob_matches(rest, path) ||
+            (0..path.len()).any(|index| path[index] == b'/' && glob_matches(rest, &path[index + 1..]));
+    }
+
+    if pattern.starts_with(b"**") {
+        let rest = &pattern[2..];
+        return (0..path.len() + 1).any(|index| glob_matches(rest, &path[index..]));
+    }
+
+    match pattern[0] {
+        b'*' => {
+            for index in 0..path.len() + 1 {
+                if glob_matches(&pattern[1..], &path[index..]) {
+                    return true;
+                }
+                if index < path.len() && path[index] == b'/' {
+                    break;
+                }
+            }
+            false
+        }
+        b'?' => !path.is_empty() && path[0] != b'/' && glob_matches(&pattern[1..], &path[1..]),
+        byte => !path.is_empty() && path[0] == byte && glob_matches(&pattern[1..], &path[1..]),
+    }
+}
+
+/// A line based diff, where every line is prefixed with "+ ", "- " or "  ".
+pub fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence, built from the end so the diff can be read from the start.
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(entries: &[(&str, f64)]) -> HashMap<String, f64> {
+        entries
+            .iter()
+            .map(|&(owner, owned)| (owner.to_owned(), owned))
+            .collect()
+    }
+
+    fn options() -> Options {
+        Options {
+            style: Style::GitHub,
+            threshold: 0.25,
+            max_owners: 2,
+            depth: 2,
+            teams: false,
+        }
+    }
+
+    #[test]
+    fn it_picks_top_owners_above_threshold() {
+        let owners = top_owners(
+            &lines(&[("@a", 50.0), ("@b", 30.0), ("@c", 20.0)]),
+            0.25,
+            3,
+        );
+        assert_eq!(owners, vec!["@a", "@b"]);
+
+        let owners = top_owners(&lines(&[("@a", 50.0), ("@b", 50.0)]), 0.25, 1);
+        assert_eq!(owners, vec!["@a"]);
+    }
+
+    #[test]
+    fn it_only_adds_rules_when_owners_change() {
+        let mut directories = BTreeMap::new();
+        directories.insert(String::new(), lines(&[("@a", 100.0)]));
+        directories.insert(String::from("docs"), lines(&[("@a", 10.0)]));
+        directories.insert(String::from("src"), lines(&[("@b", 80.0), ("@a", 20.0)]));
+        directories.insert(String::from("src/cli"), lines(&[("@b", 10.0)]));
+
+        let rules = rules_for_directories(directories, &options());
+
+        assert_eq!(
+            rules,
+            vec![
+                Rule {
+                    pattern: String::from("*"),
+                    owners: vec![String::from("@a")],
+                },
+                Rule {
+                    pattern: String::from("/src/"),
+                    owners: vec![String::from("@b")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_lists_ancestor_directories() {
+        assert_eq!(ancestors(Path::new("README.md"), 2), vec![""]);
+        assert_eq!(
+            ancestors(Path::new("src/formatters/json/mod.rs"), 2),
+            vec!["", "src", "src/formatters"]
+        );
+    }
+
+    fn rule(pattern: &str) -> Rule {
+        Rule {
+            pattern: pattern.to_owned(),
+            owners: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn it_parses_codeowners_files() {
+        let rules = parse(
+            "# Comment\n\n* @everyone\n[Backend]\n/src/ @a @org/team # trailing comment\n",
+        );
+
+        assert_eq!(
+            rules,
+            vec![
+                (
+                    3,
+                    Rule {
+                        pattern: String::from("*"),
+                        owners: vec![String::from("@everyone")],
+                    },
+                ),
+                (
+                    5,
+                    Rule {
+                        pattern: String::from("/src/"),
+                        owners: vec![String::from("@a"), String::from("@org/team")],
+                    },
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_matches_paths_like_gitignore() {
+        assert!(rule("*").matches("src/main.rs"));
+        assert!(rule("*.rs").matches("src/main.rs"));
+        assert!(!rule("*.rs").matches("README.md"));
+
+        assert!(rule("/src/").matches("src/main.rs"));
+        assert!(!rule("/src/").matches("lib/src/main.rs"));
+        assert!(!rule("/src/").matches("src"));
+        assert!(rule("src/").matches("lib/src/main.rs"));
+        assert!(rule("docs").matches("docs/index.md"));
+        assert!(rule("docs").matches("lib/docs"));
+
+        assert!(rule("/src/*.rs").matches("src/main.rs"));
+        assert!(!rule("/src/*.rs").matches("src/formatters/json.rs"));
+        assert!(rule("/src/**/*.rs").matches("src/formatters/json.rs"));
+        assert!(rule("/src/**/*.rs").matches("src/main.rs"));
+        assert!(rule("**/json.rs").matches("src/formatters/json.rs"));
+        assert!(rule("/src/ma?n.rs").matches("src/main.rs"));
+    }
+
+    #[test]
+    fn it_diffs_lines() {
+        let diff = diff_lines("* @a\n/src/ @b\n/docs/ @c\n", "* @a\n/src/ @d\n/docs/ @c\n");
+        assert_eq!(
+            diff,
+            vec!["  * @a", "- /src/ @b", "+ /src/ @d", "  /docs/ @c"]
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet};
+
+use codeowners::{with_at_sign, Rule};
+use configuration::Team;
+use ownership::FileOwnership;
+use person::{Email, Person};
+
+/// How well a single rule of a CODEOWNERS file matches who actually wrote the code it covers.
+#[derive(Debug)]
+pub struct RuleAudit<'context> {
+    pub line: usize,
+    pub rule: Rule,
+    /// Owners that are neither a known handle, email nor team handle.
+    pub unknown_owners: Vec<String>,
+    pub files: usize,
+    pub lines: u32,
+    pub lines_owned_by_listed: u32,
+    /// The people owning most of the lines the rule covers.
+    pub actual_owners: Vec<(&'context Person, u32)>,
+    /// Set when the listed owners own less than the threshold of the lines. Rules without any
+    /// lines are never weak.
+    pub weak: bool,
+    /// Set when the rule matches no files, because none exist or later rules override it.
+    pub unused: bool,
+}
+
+impl<'context> RuleAudit<'context> {
+    pub fn fraction_owned_by_listed(&self) -> f64 {
+        if self.lines > 0 {
+            f64::from(self.lines_owned_by_listed) / f64::from(self.lines)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Someone owning a big part of the code without being mentioned in the CODEOWNERS file.
+#[derive(Debug)]
+pub struct UnlistedOwner<'context> {
+    pub person: &'context Person,
+    pub lines: u32,
+    pub fraction_owned: f64,
+}
+
+#[derive(Debug)]
+pub struct CodeOwnersAudit<'context> {
+    pub path: String,
+    pub threshold: f64,
+    pub rules: Vec<RuleAudit<'context>>,
+    pub unowned_files: Vec<String>,
+    pub unlisted_owners: Vec<UnlistedOwner<'context>>,
+}
+
+impl<'context> CodeOwnersAudit<'context> {
+    pub fn weak_rules(&self) -> Vec<&RuleAudit<'context>> {
+        self.rules.iter().filter(|rule| rule.weak).collect()
+    }
+
+    pub fn unused_rules(&self) -> Vec<&RuleAudit<'context>> {
+        self.rules.iter().filter(|rule| rule.unused).collect()
+    }
+}
+
+#[derive(Default)]
+struct RuleLines<'context> {
+    files: usize,
+    lines: u32,
+    lines_per_person: HashMap<&'context Person, u32>,
+}
+
+/// Compares the rules of a CODEOWNERS file with the blame of the files they match. Like the code
+/// hosting services do, the last matching rule decides the owners of a file.
+///
+/// Rules where the listed owners own less than `threshold` of the lines are weak, and people
+/// owning at least `min_share` of the repository must be listed somewhere.
+pub fn calculate<'context>(
+    path: String,
+    rules: Vec<(usize, Rule)>,
+    files: &[FileOwnership<'context>],
+    people: &[&'context Person],
+    teams: &[Team],
+    threshold: f64,
+    min_share: f64,
+) -> CodeOwnersAudit<'context> {
+    let mut rule_lines: Vec<RuleLines> = rules.iter().map(|_| RuleLines::default()).collect();
+    let mut unowned_files = Vec::new();
+    let mut total_lines = 0;
+    let mut lines_per_person: HashMap<&Person, u32> = HashMap::new();
+
+    for file in files {
+        let path = file.path.to_string_lossy().into_owned();
+        let file_lines = file.lines_per_person();
+
+        total_lines += file.total_lines();
+        for (person, lines) in &file_lines {
+            *lines_per_person.entry(person).or_insert(0) += *lines;
+        }
+
+        match rules.iter().rposition(|&(_, ref rule)| rule.matches(&path)) {
+            Some(index) => {
+                let matched = &mut rule_lines[index];
+                matched.files += 1;
+                matched.lines += file.total_lines();
+                for (person, lines) in file_lines {
+                    *matched.lines_per_person.entry(person).or_insert(0) += lines;
+                }
+            }
+            None => unowned_files.push(path),
+        }
+    }
+
+    let mut listed_anywhere: HashSet<&Person> = HashSet::new();
+    let mut audited_rules = Vec::new();
+
+    for ((line, rule), matched) in rules.into_iter().zip(rule_lines) {
+        let mut listed: HashSet<&Person> = HashSet::new();
+        let mut unknown_owners = Vec::new();
+
+        for owner in &rule.owners {
+            match resolve_owner(owner, people, teams) {
+                Some(owners) => listed.extend(owners),
+                None => unknown_owners.push(owner.clone()),
+            }
+        }
+
+        let lines_owned_by_listed = matched
+            .lines_per_person
+            .iter()
+            .filter(|&(person, _)| listed.contains(person))
+            .map(|(_, lines)| lines)
+            .sum();
+
+        let mut actual_owners: Vec<(&Person, u32)> = matched.lines_per_person.into_iter().collect();
+        // Note: Reverse sort
+        actual_owners.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        actual_owners.truncate(3);
+
+        listed_anywhere.extend(listed);
+
+        let mut audit = RuleAudit {
+            line: line,
+            rule: rule,
+            unknown_owners: unknown_owners,
+            files: matched.files,
+            lines: matched.lines,
+            lines_owned_by_listed: lines_owned_by_listed,
+            actual_owners: actual_owners,
+            weak: false,
+            unused: matched.files == 0,
+        };
+        audit.weak = audit.lines > 0 && audit.fraction_owned_by_listed() < threshold;
+        audited_rules.push(audit);
+    }
+
+    let mut unlisted_owners: Vec<UnlistedOwner> = lines_per_person
+        .into_iter()
+        .filter(|&(person, _)| !listed_anywhere.contains(person))
+        .map(|(person, lines)| {
+            UnlistedOwner {
+                person: person,
+                lines: lines,
+                fraction_owned: f64::from(lines) / f64::from(total_lines.max(1)),
+            }
+        })
+        .filter(|owner| owner.fraction_owned >= min_share)
+        .collect();
+    // Note: Reverse sort
+    unlisted_owners.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.person.cmp(b.person)));
+
+    CodeOwnersAudit {
+        path: path,
+        threshold: threshold,
+        rules: audited_rules,
+        unowned_files: unowned_files,
+        unlisted_owners: unlisted_owners,
+    }
+}
+
+/// Finds the people an owner in a CODEOWNERS file refers to: a person by handle or email, or all
+/// members of a team by the team's handle.
+fn resolve_owner<'context>(
+    owner: &str,
+    people: &[&'context Person],
+    teams: &[Team],
+) -> Option<Vec<&'context Person>> {
+    let email = Email::from(owner);
+    let matching_people: Vec<&Person> = people
+        .iter()
+        .cloned()
+        .filter(|person| {
+            person.handle().map_or(false, |handle| with_at_sign(handle) == owner) ||
+                person.has_email(&email)
+        })
+        .collect();
+
+    if !matching_people.is_empty() {
+        return Some(matching_people);
+    }
+
+    teams
+        .iter()
+        .find(|team| {
+            team.handle.as_ref().map_or(
+                false,
+                |handle| with_at_sign(handle) == owner,
+            )
+        })
+        .map(|team| {
+            people
+                .iter()
+                .cloned()
+                .filter(|person| {
+                    person.team_memberships().iter().any(
+                        |&(name, _)| name == team.name,
+                    )
+                })
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Oid;
+    use ownership::OwnedHunk;
+    use std::path::PathBuf;
+
+    fn person(name: &str, handle: Option<&str>, team: Option<&str>) -> Person {
+        let mut person = Person::new(name);
+        person.add_email(format!("{}@example.com", name.to_lowercase()));
+        if let Some(handle) = handle {
+            person.set_handle(Some(handle.to_owned()));
+        }
+        if let Some(team) = team {
+            person.add_team(team, None);
+        }
+        person
+    }
+
+    fn file<'a>(path: &str, hunks: &[(&'a Person, u32)]) -> FileOwnership<'a> {
+        FileOwnership {
+            path: PathBuf::from(path),
+            hunks: hunks
+                .iter()
+                .map(|&(person, lines)| {
+                    OwnedHunk {
+                        person: person,
+                        lines: lines,
+                        start_line: 1,
+                        commit: Oid::zero(),
+                        day: 0,
+                        co_authors: Vec::new(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn rule(line: usize, pattern: &str, owners: &[&str]) -> (usize, Rule) {
+        (
+            line,
+            Rule {
+                pattern: pattern.to_owned(),
+                owners: owners.iter().map(|owner| owner.to_string()).collect(),
+            },
+        )
+    }
+
+    #[test]
+    fn it_audits_rules_against_ownership() {
+        let alice = person("Alice", Some("alice"), None);
+        let bob = person("Bob", None, Some("Backend"));
+        let carol = person("Carol", None, None);
+        let teams = vec![
+            Team {
+                name: String::from("Backend"),
+                handle: Some(String::from("@org/backend")),
+            },
+        ];
+
+        let files = vec![
+            file("src/main.rs", &[(&alice, 90), (&bob, 10)]),
+            file("server/api.rs", &[(&bob, 100)]),
+            file("README.md", &[(&carol, 50)]),
+        ];
+        let rules = vec![
+            rule(1, "/src/", &["@alice"]),
+            rule(2, "/server/", &["carol@example.com", "@ghost"]),
+            rule(3, "/server/*.rs", &["@org/backend"]),
+        ];
+
+        let audit = calculate(
+            String::from("CODEOWNERS"),
+            rules,
+            &files,
+            &[&alice, &bob, &carol],
+            &teams,
+            0.5,
+            0.1,
+        );
+
+        assert_eq!(audit.rules[0].lines_owned_by_listed, 90);
+        assert!(!audit.rules[0].weak);
+        assert!(!audit.rules[0].unused);
+
+        // Shadowed by the more specific rule below it
+        assert_eq!(audit.rules[1].files, 0);
+        assert!(audit.rules[1].unused);
+        assert!(!audit.rules[1].weak);
+        assert_eq!(audit.rules[1].unknown_owners, vec![String::from("@ghost")]);
+
+        assert_eq!(audit.rules[2].lines_owned_by_listed, 100);
+        assert_eq!(audit.rules[2].actual_owners, vec![(&bob, 100)]);
+
+        assert_eq!(audit.unowned_files, vec![String::from("README.md")]);
+        assert!(audit.unlisted_owners.is_empty());
+    }
+
+    #[test]
+    fn it_finds_big_owners_listed_nowhere() {
+        let alice = person("Alice", Some("alice"), None);
+        let bob = person("Bob", None, None);
+
+        let files = vec![file("src/main.rs", &[(&alice, 50), (&bob, 50)])];
+
+        let audit = calculate(
+            String::from("CODEOWNERS"),
+            vec![rule(1, "*", &["@alice"])],
+            &files,
+            &[&alice, &bob],
+            &[],
+            0.5,
+            0.1,
+        );
+
+        assert_eq!(audit.unlisted_owners.len(), 1);
+        assert_eq!(audit.unlisted_owners[0].person, &bob);
+        assert_eq!(audit.unlisted_owners[0].fraction_owned, 0.5);
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::errors::*;
+use super::Context;
+use coauthors;
+use count;
+use dates;
+use history::{self, CommitWalker, RenameTracker, Selection};
+use person::Person;
+
+/// Someone in the graph; a person, or a team when the graph is collapsed to teams.
+#[derive(Debug, Serialize)]
+pub struct Node {
+    pub name: String,
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+}
+
+/// A connection between two nodes. Weights are fractional in team graphs, where people in several
+/// teams have their collaboration split over them.
+#[derive(Debug, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    /// Files both sides changed within the time window of each other.
+    #[serde(serialize_with = "count::serialize")]
+    pub shared_files: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub co_authored_commits: f64,
+}
+
+impl Edge {
+    pub fn weight(&self) -> f64 {
+        self.shared_files + self.co_authored_commits
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollaborationGraph {
+    pub window_days: i64,
+    pub teams: bool,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl CollaborationGraph {
+    /// Renders the graph in the Graphviz DOT language, with thicker lines for stronger edges.
+    pub fn to_dot(&self) -> String {
+        let max_weight = self.edges.iter().map(Edge::weight).fold(0.0, f64::max);
+
+        let mut dot = String::from("graph collaboration {\n");
+        dot.push_str("  node [shape=box];\n");
+        for node in &self.nodes {
+            dot.push_str(&format!("  {};\n", dot_id(&node.name)));
+        }
+        for edge in &self.edges {
+            let penwidth = if max_weight > 0.0 {
+                1.0 + 4.0 * edge.weight() / max_weight
+            } else {
+                1.0
+            };
+            dot.push_str(&format!(
+                "  {} -- {} [weight={:.2}, penwidth={:.2}];\n",
+                dot_id(&edge.from),
+                dot_id(&edge.to),
+                edge.weight(),
+                penwidth
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Debug, Default)]
+struct EdgeScore {
+    shared_files: f64,
+    co_authored_commits: f64,
+}
+
+type PersonPair<'context> = (&'context Person, &'context Person);
+
+fn pair<'context>(a: &'context Person, b: &'context Person) -> PersonPair<'context> {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+/// Connects people that changed the same file within `window_days` of each other, or that
+/// co-authored a commit. With `teams` set, the graph is collapsed to the teams of those people.
+pub fn calculate(
+    context: &Context,
+    selection: &Selection,
+    window_days: i64,
+    teams: bool,
+) -> Result<CollaborationGraph> {
+    let people_db = context.people_db();
+    let repo = context.repo();
+
+    let mut renames = RenameTracker::new();
+    let mut touches: HashMap<PathBuf, Vec<(i64, &Person)>> = HashMap::new();
+    let mut commits: HashMap<&Person, f64> = HashMap::new();
+    let mut edges: HashMap<PersonPair, EdgeScore> = HashMap::new();
+
+    for commit in CommitWalker::new(repo, &selection.without_dates())? {
+        if history::is_merge(&commit) {
+            continue;
+        }
+
+        let changes = history::changed_files(repo, &commit)?;
+        // Renames are followed outside of the selected dates too, or older changes would be lost.
+        let paths: Vec<PathBuf> = changes.iter().map(|change| renames.follow(change)).collect();
+        if !selection.includes(&commit) {
+            continue;
+        }
+
+        let mut contributors = vec![people_db.find_by_signature(commit.author())?];
+        for co_author in coauthors::co_authors(people_db, &commit) {
+            if !contributors.contains(&co_author) {
+                contributors.push(co_author);
+            }
+        }
+
+        for (index, a) in contributors.iter().enumerate() {
+            *commits.entry(a).or_insert(0.0) += 1.0;
+            for b in &contributors[index + 1..] {
+                edges.entry(pair(a, b)).or_insert_with(Default::default).co_authored_commits += 1.0;
+            }
+        }
+
+        let day = dates::local_day(&commit.author().when());
+        for path in paths {
+            let file_touches = touches.entry(path).or_insert_with(Vec::new);
+            for person in &contributors {
+                file_touches.push((day, person));
+            }
+        }
+    }
+
+    for (_, mut file_touches) in touches {
+        for shared in shared_within_window(&mut file_touches, window_days) {
+            edges.entry(shared).or_insert_with(Default::default).shared_files += 1.0;
+        }
+    }
+
+    if teams {
+        Ok(collapse_to_teams(window_days, commits, edges))
+    } else {
+        Ok(build_graph(
+            window_days,
+            false,
+            commits.into_iter().map(|(person, commits)| (person.name().to_owned(), commits)),
+            edges.into_iter().map(|((a, b), score)| {
+                (a.name().to_owned(), b.name().to_owned(), score)
+            }),
+        ))
+    }
+}
+
+/// The pairs of different people that changed a file within `window_days` of each other.
+fn shared_within_window<'context>(
+    touches: &mut Vec<(i64, &'context Person)>,
+    window_days: i64,
+) -> HashSet<PersonPair<'context>> {
+    touches.sort_by_key(|&(day, _)| day);
+
+    let mut pairs = HashSet::new();
+    for (index, &(day, a)) in touches.iter().enumerate() {
+        for &(other_day, b) in touches[index + 1..].iter() {
+            if other_day - day > window_days {
+                break;
+            }
+            if a != b {
+                pairs.insert(pair(a, b));
+            }
+        }
+    }
+    pairs
+}
+
+fn team_name(name: Option<&str>) -> String {
+    name.unwrap_or("(No team)").to_owned()
+}
+
+/// Memberships of a person, where people without a team are counted as "no team".
+fn memberships(person: &Person) -> Vec<(Option<&str>, f64)> {
+    let memberships = person.team_memberships();
+    if memberships.is_empty() {
+        vec![(None, 1.0)]
+    } else {
+        memberships
+            .into_iter()
+            .map(|(name, weight)| (Some(name), weight))
+            .collect()
+    }
+}
+
+fn collapse_to_teams(
+    window_days: i64,
+    commits: HashMap<&Person, f64>,
+    edges: HashMap<PersonPair, EdgeScore>,
+) -> CollaborationGraph {
+    let mut team_commits: HashMap<String, f64> = HashMap::new();
+    for (person, commits) in commits {
+        for (name, weight) in memberships(person) {
+            *team_commits.entry(team_name(name)).or_insert(0.0) += commits * weight;
+        }
+    }
+
+    let mut team_edges: HashMap<(String, String), EdgeScore> = HashMap::new();
+    for ((a, b), score) in edges {
+        for (a_team, a_weight) in memberships(a) {
+            for (b_team, b_weight) in memberships(b) {
+                // Collaboration within a team is not an edge in the team graph.
+                if a_team == b_team {
+                    continue;
+                }
+                let key = if a_team < b_team {
+                    (team_name(a_team), team_name(b_team))
+                } else {
+                    (team_name(b_team), team_name(a_team))
+                };
+                let team_score = team_edges.entry(key).or_insert_with(Default::default);
+                team_score.shared_files += score.shared_files * a_weight * b_weight;
+                team_score.co_authored_commits += score.co_authored_commits * a_weight * b_weight;
+            }
+        }
+    }
+
+    build_graph(
+        window_days,
+        true,
+        team_commits.into_iter(),
+        team_edges.into_iter().map(|((a, b), score)| (a, b, score)),
+    )
+}
+
+fn build_graph<N, E>(window_days: i64, teams: bool, nodes: N, edges: E) -> CollaborationGraph
+where
+    N: Iterator<Item = (String, f64)>,
+    E: Iterator<Item = (String, String, EdgeScore)>,
+{
+    let nodes: BTreeMap<String, f64> = nodes.collect();
+
+    let mut edges: Vec<Edge> = edges
+        .map(|(from, to, score)| {
+            Edge {
+                from: from,
+                to: to,
+                shared_files: score.shared_files,
+                co_authored_commits: score.co_authored_commits,
+            }
+        })
+        .collect();
+    // Note: Reverse sort
+    edges.sort_by(|a, b| {
+        b.weight()
+            .partial_cmp(&a.weight())
+            .unwrap_or(::std::cmp::Ordering::Equal)
+            .then_with(|| a.from.cmp(&b.from))
+            .then_with(|| a.to.cmp(&b.to))
+    });
+
+    CollaborationGraph {
+        window_days: window_days,
+        teams: teams,
+        nodes: nodes
+            .into_iter()
+            .map(|(name, commits)| {
+                Node {
+                    name: name,
+                    commits: commits,
+                }
+            })
+            .collect(),
+        edges: edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_connects_people_changing_a_file_within_the_window() {
+        let alice = Person::new("Alice");
+        let bob = Person::new("Bob");
+        let carol = Person::new("Carol");
+
+        let mut touches = vec![(0, &alice), (40, &carol), (5, &bob), (3, &alice)];
+        let pairs = shared_within_window(&mut touches, 30);
+
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs.contains(&(&alice, &bob)));
+    }
+
+    #[test]
+    fn it_collapses_people_into_teams() {
+        let mut alice = Person::new("Alice");
+        alice.add_team("Backend", None);
+        let mut bob = Person::new("Bob");
+        bob.add_team("Backend", Some(1.0));
+        bob.add_team("Frontend", Some(1.0));
+        let carol = Person::new("Carol");
+
+        let mut commits = HashMap::new();
+        commits.insert(&alice, 2.0);
+        commits.insert(&bob, 2.0);
+        commits.insert(&carol, 1.0);
+
+        let mut edges = HashMap::new();
+        edges.insert(
+            pair(&alice, &bob),
+            EdgeScore {
+                shared_files: 4.0,
+                co_authored_commits: 0.0,
+            },
+        );
+        edges.insert(
+            pair(&bob, &carol),
+            EdgeScore {
+                shared_files: 0.0,
+                co_authored_commits: 2.0,
+            },
+        );
+
+        let graph = collapse_to_teams(30, commits, edges);
+
+        let nodes: Vec<(&str, f64)> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.name.as_str(), node.commits))
+            .collect();
+        assert_eq!(nodes, vec![("(No team)", 1.0), ("Backend", 3.0), ("Frontend", 1.0)]);
+
+        let edges: Vec<(&str, &str, f64)> = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str(), edge.weight()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("Backend", "Frontend", 2.0),
+                ("(No team)", "Backend", 1.0),
+                ("(No team)", "Frontend", 1.0),
+            ]
+        );
+    }
+}
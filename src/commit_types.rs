@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use super::errors::*;
+use super::Context;
+use count;
+use conventional;
+use history::{self, CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+/// The type commits are counted under when their subject does not follow Conventional Commits.
+pub static OTHER_TYPE: &'static str = "other";
+
+/// The scope breaking changes without a scope are counted under.
+pub static NO_SCOPE: &'static str = "(no scope)";
+
+#[derive(Debug)]
+pub struct CommitTypeStatistics<'context> {
+    pub repository: TypeScore,
+    pub breaking_changes_by_scope: BTreeMap<String, u32>,
+    pub combined_tracking: CombinedTracking<'context, TypeScore>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedTypes {
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+    #[serde(serialize_with = "count::serialize_map")]
+    pub types: BTreeMap<String, f64>,
+    #[serde(serialize_with = "count::serialize")]
+    pub breaking_changes: f64,
+}
+
+impl ComputedTypes {
+    pub fn count(&self, kind: &str) -> f64 {
+        self.types.get(kind).cloned().unwrap_or(0.0)
+    }
+}
+
+impl<'context> CommitTypeStatistics<'context> {
+    pub fn repository_types(&self) -> ComputedTypes {
+        self.repository.compute()
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedTypes)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, score)| (*person, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedTypes)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, score)| (team_name, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+}
+
+/// Commits per type of a person or team. Teams only get their share of the commits of people
+/// that are in several teams, so their counts can be fractional.
+#[derive(Debug, Default)]
+pub struct TypeScore {
+    commits: f64,
+    types: BTreeMap<String, f64>,
+    breaking_changes: f64,
+}
+
+impl TypeScore {
+    fn add_commit(&mut self, kind: &str, breaking: bool, share: f64) {
+        self.commits += share;
+        *self.types.entry(kind.to_owned()).or_insert(0.0) += share;
+        if breaking {
+            self.breaking_changes += share;
+        }
+    }
+
+    fn compute(&self) -> ComputedTypes {
+        ComputedTypes {
+            commits: self.commits,
+            types: self.types.clone(),
+            breaking_changes: self.breaking_changes,
+        }
+    }
+}
+
+/// The type, scope and whether the commit is a breaking change. Types are lowercased so that
+/// "Fix" and "fix" end up together.
+fn classify(subject: &str, message: &str) -> (String, Option<String>, bool) {
+    match conventional::parse(subject) {
+        Some(parsed) => {
+            (
+                parsed.kind.to_lowercase(),
+                parsed.scope.map(String::from),
+                parsed.breaking || conventional::has_breaking_footer(message),
+            )
+        }
+        None => (String::from(OTHER_TYPE), None, false),
+    }
+}
+
+/// Counts a breaking change under its scope, or under `NO_SCOPE` when it has none.
+fn add_breaking_change(by_scope: &mut BTreeMap<String, u32>, scope: Option<String>) {
+    let scope = scope.unwrap_or_else(|| String::from(NO_SCOPE));
+    *by_scope.entry(scope).or_insert(0) += 1;
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<CommitTypeStatistics<'context>> {
+    let people_db = context.people_db();
+
+    let mut repository = TypeScore::default();
+    let mut breaking_changes_by_scope = BTreeMap::new();
+    let mut tracking: CombinedTracking<TypeScore> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        // Merge commits mostly have generated messages.
+        if history::is_merge(&commit) {
+            continue;
+        }
+
+        let person = people_db.find_by_signature(commit.author())?;
+        let (kind, scope, breaking) =
+            classify(commit.summary().unwrap_or(""), commit.message().unwrap_or(""));
+
+        repository.add_commit(&kind, breaking, 1.0);
+        tracking.track_person_weighted(
+            person,
+            |score, weight| score.add_commit(&kind, breaking, weight),
+        );
+
+        if breaking {
+            add_breaking_change(&mut breaking_changes_by_scope, scope);
+        }
+    }
+
+    Ok(CommitTypeStatistics {
+        repository: repository,
+        breaking_changes_by_scope: breaking_changes_by_scope,
+        combined_tracking: tracking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_classifies_commits() {
+        assert_eq!(
+            classify("Fix(Parser): Handle tabs", "Fix(Parser): Handle tabs"),
+            (String::from("fix"), Some(String::from("Parser")), false)
+        );
+        assert_eq!(
+            classify("Update the readme", "Update the readme"),
+            (String::from(OTHER_TYPE), None, false)
+        );
+    }
+
+    #[test]
+    fn it_finds_breaking_changes() {
+        assert!(classify("feat(api)!: Drop v1", "feat(api)!: Drop v1").2);
+        assert_eq!(
+            classify(
+                "feat: New config format",
+                "feat: New config format\n\nBREAKING CHANGE: Old files must be converted",
+            ),
+            (String::from("feat"), None, true)
+        );
+        // Only Conventional Commits can be breaking changes.
+        assert!(
+            !classify("Rewrite everything", "Rewrite everything\n\nBREAKING CHANGE: All of it").2
+        );
+    }
+
+    #[test]
+    fn it_counts_types_and_breaking_changes_by_scope() {
+        let subjects = [
+            "feat(api)!: Drop v1",
+            "fix(api): Handle timeouts",
+            "feat!: Require a config file",
+            "refactor(api)!: Rename endpoints",
+            "Update the readme",
+        ];
+
+        let mut score = TypeScore::default();
+        let mut by_scope = BTreeMap::new();
+        for subject in &subjects {
+            let (kind, scope, breaking) = classify(subject, subject);
+            score.add_commit(&kind, breaking, 0.5);
+            if breaking {
+                add_breaking_change(&mut by_scope, scope);
+            }
+        }
+
+        let computed = score.compute();
+        assert_eq!(computed.commits, 2.5);
+        assert_eq!(computed.count("feat"), 1.0);
+        assert_eq!(computed.count("fix"), 0.5);
+        assert_eq!(computed.count(OTHER_TYPE), 0.5);
+        assert_eq!(computed.count("chore"), 0.0);
+        assert_eq!(computed.breaking_changes, 1.5);
+
+        let by_scope: Vec<(&str, u32)> = by_scope
+            .iter()
+            .map(|(scope, count)| (scope.as_str(), *count))
+            .collect();
+        assert_eq!(by_scope, vec![(NO_SCOPE, 1), ("api", 2)]);
+    }
+}
@@ -0,0 +1,126 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use git2::Commit;
+
+use super::errors::*;
+use super::Context;
+use coauthors;
+use count;
+use dates;
+use history::{CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+#[derive(Debug)]
+pub struct CommitStatistics<'context> {
+    pub repository: CommitScore,
+    pub combined_tracking: CombinedTracking<'context, CommitScore>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedCommitActivity {
+    /// Commits credited, which is fractional when credit for co-authored commits is split.
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+    pub first_commit: Option<String>,
+    pub last_commit: Option<String>,
+    pub active_days: usize,
+    pub commits_per_active_day: f64,
+}
+
+impl<'context> CommitStatistics<'context> {
+    pub fn repository_activity(&self) -> ComputedCommitActivity {
+        self.repository.compute()
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedCommitActivity)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, score)| (*person, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedCommitActivity)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, score)| (team_name, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CommitScore {
+    commits: f64,
+    first_day: Option<i64>,
+    last_day: Option<i64>,
+    active_days: HashSet<i64>,
+}
+
+impl CommitScore {
+    fn add_commit(&mut self, commit: &Commit, share: f64) {
+        let day = dates::local_day(&commit.author().when());
+
+        self.commits += share;
+        self.active_days.insert(day);
+        self.first_day = Some(self.first_day.map_or(day, |first| first.min(day)));
+        self.last_day = Some(self.last_day.map_or(day, |last| last.max(day)));
+    }
+
+    fn compute(&self) -> ComputedCommitActivity {
+        let active_days = self.active_days.len();
+        ComputedCommitActivity {
+            commits: self.commits,
+            first_commit: self.first_day.map(dates::format_day),
+            last_commit: self.last_day.map(dates::format_day),
+            active_days: active_days,
+            commits_per_active_day: if active_days > 0 {
+                self.commits / active_days as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<CommitStatistics<'context>> {
+    let people_db = context.people_db();
+    let credit_mode = context.credit_mode();
+
+    let mut repository = CommitScore::default();
+    let mut tracking: CombinedTracking<CommitScore> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        let person = people_db.find_by_signature(commit.author())?;
+        let co_authors = if credit_mode.is_primary() {
+            Vec::new()
+        } else {
+            coauthors::co_authors(people_db, &commit)
+        };
+
+        repository.add_commit(&commit, 1.0);
+        for (contributor, share) in coauthors::credit(person, &co_authors, credit_mode) {
+            tracking.track_person_weighted(
+                contributor,
+                |score, weight| score.add_commit(&commit, share * weight),
+            );
+        }
+    }
+
+    Ok(CommitStatistics {
+        repository: repository,
+        combined_tracking: tracking,
+    })
+}
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use git2::Signature;
 
+use coauthors::CreditMode;
 use person::*;
 use super::errors::*;
 
@@ -9,6 +10,21 @@ use super::errors::*;
 pub struct Configuration {
     pub generated_at_sha: String,
     pub people: Vec<Person>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teams: Vec<Team>,
+    /// How commits with `Co-authored-by:` trailers are credited.
+    #[serde(default, skip_serializing_if = "CreditMode::is_primary")]
+    pub credit: CreditMode,
+}
+
+/// Extra information about a team. Teams exist as soon as a person is put in them, so this is only
+/// needed for teams with something more to say than their name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    /// The name of the team on the code hosting service, like "@acme/backend".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
 }
 
 impl Configuration {
@@ -27,6 +43,9 @@ pub struct ConfigurationBuilder {
 
     seen_emails: HashSet<String>,
     people_by_name: HashMap<String, Person>,
+    teams: Vec<Team>,
+    credit: CreditMode,
+    joined: Option<String>,
 }
 
 impl ConfigurationBuilder {
@@ -44,17 +63,30 @@ impl ConfigurationBuilder {
         self.generated_at_sha = Some(commit_sha);
     }
 
+    /// Marks everyone added from now on as having joined on the given date.
+    pub fn set_joined_date(&mut self, date: String) {
+        self.joined = Some(date);
+    }
+
     pub fn add_author<'a>(&mut self, author: Signature<'a>) {
-        if let Some(email) = author.email() {
-            if !self.seen_emails.contains(email) {
-                if let Some(name) = author.name() {
-                    self.seen_emails.insert(email.into());
-                    self.people_by_name
-                        .entry(name.to_owned())
-                        .or_insert_with(|| Person::new(name))
-                        .add_email(email);
-                }
-            }
+        if let (Some(name), Some(email)) = (author.name(), author.email()) {
+            self.add_identity(name, email);
+        }
+    }
+
+    /// Adds someone known only by name and email, like a co-author from a commit trailer.
+    pub fn add_identity(&mut self, name: &str, email: &str) {
+        if !self.seen_emails.contains(email) {
+            self.seen_emails.insert(email.into());
+            let joined = &self.joined;
+            self.people_by_name
+                .entry(name.to_owned())
+                .or_insert_with(|| {
+                    let mut person = Person::new(name);
+                    person.set_joined(joined.clone());
+                    person
+                })
+                .add_email(email);
         }
     }
 
@@ -69,6 +101,8 @@ impl ConfigurationBuilder {
         Ok(Configuration {
             generated_at_sha: self.generated_at_sha.unwrap(),
             people: people,
+            teams: self.teams,
+            credit: self.credit,
         })
     }
 
@@ -84,6 +118,9 @@ impl ConfigurationBuilder {
 
             self.people_by_name.insert(name, person);
         }
+
+        self.teams = config.teams;
+        self.credit = config.credit;
     }
 }
 
@@ -129,6 +166,44 @@ mod tests {
         assert!(john.emails().contains(&email("john.doe@example.com")));
     }
 
+    #[test]
+    fn it_adds_co_authors_as_people() {
+        let mut builder = ConfigurationBuilder::new();
+
+        builder.set_latest_commit_sha(String::from("deadbeef"));
+        builder.add_author(git_signature("Jane Doe", "jane.doe@example.com"));
+        builder.add_identity("Jane Doe", "jane@pairing.example.com");
+        builder.add_identity("John Doe", "john.doe@example.com");
+
+        let config = builder.into_configuration().unwrap();
+        let people_db = config.people_db().unwrap();
+
+        assert_eq!(people_db.len(), 2);
+        let jane = people_db.find_by_email(&email("jane@pairing.example.com")).unwrap();
+        assert_eq!(jane.emails().len(), 2);
+    }
+
+    #[test]
+    fn it_marks_new_people_as_joined() {
+        let mut existing = ConfigurationBuilder::new();
+        existing.set_latest_commit_sha(String::from("deadbeef"));
+        existing.add_author(git_signature("Jane Doe", "jane.doe@example.com"));
+
+        let mut builder = ConfigurationBuilder::from_existing(existing.into_configuration().unwrap());
+        builder.set_joined_date(String::from("2017-10-01"));
+        builder.add_author(git_signature("Jane Doe", "jane@example.com"));
+        builder.add_author(git_signature("John Doe", "john.doe@example.com"));
+
+        let config = builder.into_configuration().unwrap();
+
+        let joined: Vec<(&str, Option<&str>)> = config
+            .people
+            .iter()
+            .map(|p| (p.name(), p.joined()))
+            .collect();
+        assert_eq!(joined, vec![("Jane Doe", None), ("John Doe", Some("2017-10-01"))]);
+    }
+
     #[test]
     fn it_sorts_people() {
         let mut builder = ConfigurationBuilder::new();
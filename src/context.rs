@@ -7,12 +7,16 @@ use std::path::PathBuf;
 use git2::{Commit, Repository};
 
 use super::Configuration;
+use coauthors::CreditMode;
+use configuration::Team;
 use person::PeopleDatabase;
 use super::errors::*;
 
 pub struct Context {
     repository: Repository,
     people_db: PeopleDatabase,
+    teams: Vec<Team>,
+    credit_mode: CreditMode,
 }
 
 pub fn config_file_path(repo: &Repository) -> PathBuf {
@@ -28,13 +32,38 @@ impl Context {
         Ok(Context {
             repository: repo,
             people_db: people_db,
+            teams: config.teams,
+            credit_mode: config.credit,
         })
     }
 
+    /// A context without a config file, for tests on throwaway repositories.
+    #[cfg(test)]
+    pub fn new(repository: Repository, people_db: PeopleDatabase) -> Context {
+        Context {
+            repository: repository,
+            people_db: people_db,
+            teams: Vec::new(),
+            credit_mode: CreditMode::default(),
+        }
+    }
+
     pub fn people_db(&self) -> &PeopleDatabase {
         &self.people_db
     }
 
+    pub fn credit_mode(&self) -> CreditMode {
+        self.credit_mode
+    }
+
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.iter().find(|team| team.name == name)
+    }
+
     pub fn repo(&self) -> &Repository {
         &self.repository
     }
@@ -0,0 +1,98 @@
+/// A commit subject following the Conventional Commits format, like `feat(parser)!: Add trailers`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConventionalSubject<'a> {
+    pub kind: &'a str,
+    pub scope: Option<&'a str>,
+    /// Set when the type is followed by a "!".
+    pub breaking: bool,
+    pub description: &'a str,
+}
+
+/// Parses a commit subject, returning `None` when it does not follow Conventional Commits.
+pub fn parse(subject: &str) -> Option<ConventionalSubject> {
+    let colon = match subject.find(": ") {
+        Some(colon) => colon,
+        None => return None,
+    };
+    let description = subject[colon + 2..].trim();
+    let mut prefix = &subject[0..colon];
+
+    let breaking = prefix.ends_with('!');
+    if breaking {
+        prefix = &prefix[0..prefix.len() - 1];
+    }
+
+    let (kind, scope) = match prefix.find('(') {
+        Some(open) if prefix.ends_with(')') => {
+            (&prefix[0..open], Some(&prefix[open + 1..prefix.len() - 1]))
+        }
+        Some(_) => return None,
+        None => (prefix, None),
+    };
+
+    let valid_kind = !kind.is_empty() &&
+        kind.chars().all(|c| c.is_ascii_alphabetic() || c == '-');
+    let valid_scope = scope.map_or(true, |scope| {
+        !scope.is_empty() && !scope.contains('(') && !scope.contains(')')
+    });
+
+    if valid_kind && valid_scope && !description.is_empty() {
+        Some(ConventionalSubject {
+            kind: kind,
+            scope: scope,
+            breaking: breaking,
+            description: description,
+        })
+    } else {
+        None
+    }
+}
+
+/// Checks for a `BREAKING CHANGE:` footer, which marks a breaking change just like a "!" does.
+pub fn has_breaking_footer(message: &str) -> bool {
+    message.lines().any(|line| {
+        line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_conventional_subjects() {
+        assert_eq!(
+            parse("feat(parser)!: Add trailers"),
+            Some(ConventionalSubject {
+                kind: "feat",
+                scope: Some("parser"),
+                breaking: true,
+                description: "Add trailers",
+            })
+        );
+        assert_eq!(
+            parse("fix: Handle empty files"),
+            Some(ConventionalSubject {
+                kind: "fix",
+                scope: None,
+                breaking: false,
+                description: "Handle empty files",
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_other_subjects() {
+        assert_eq!(parse("Fix the build"), None);
+        assert_eq!(parse("Release 1.0: the big one"), None);
+        assert_eq!(parse("feat(: Broken scope"), None);
+        assert_eq!(parse("feat():  "), None);
+        assert_eq!(parse("fix: "), None);
+    }
+
+    #[test]
+    fn it_finds_breaking_change_footers() {
+        assert!(has_breaking_footer("feat: New API\n\nBREAKING CHANGE: The old one is gone"));
+        assert!(!has_breaking_footer("feat: New API\n\nNot a BREAKING CHANGE: really"));
+    }
+}
@@ -0,0 +1,59 @@
+//! Counts of commits and lines, which people and the repository always get whole and teams can
+//! get a share of.
+
+extern crate serde;
+
+use std::collections::BTreeMap;
+
+use self::serde::{Serialize, Serializer};
+
+/// A count that is written as an integer when it is whole, so only the shares of people in several
+/// teams show up as fractions in JSON and YAML.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Count(pub f64);
+
+impl Serialize for Count {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Count(count) = *self;
+        if count.fract() == 0.0 && count.abs() < i64::max_value() as f64 {
+            serializer.serialize_i64(count as i64)
+        } else {
+            serializer.serialize_f64(count)
+        }
+    }
+}
+
+pub fn counts(values: &[f64]) -> Vec<Count> {
+    values.iter().map(|&value| Count(value)).collect()
+}
+
+/// For `#[serde(serialize_with = "count::serialize")]` on count fields.
+pub fn serialize<S>(count: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Count(*count).serialize(serializer)
+}
+
+pub fn serialize_map<K, S>(counts: &BTreeMap<K, f64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    S: Serializer,
+{
+    serializer.collect_map(counts.iter().map(|(key, &count)| (key, Count(count))))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+
+    use super::*;
+
+    #[test]
+    fn it_writes_whole_counts_as_integers() {
+        assert_eq!(serde_json::to_string(&counts(&[3.0, 2.5, -4.0, 0.0])).unwrap(), "[3,2.5,-4,0]");
+    }
+}
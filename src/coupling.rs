@@ -0,0 +1,202 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::errors::*;
+use super::{TreeWalker, Context};
+use history::{self, CommitWalker, RenameTracker, Selection};
+
+#[derive(Debug)]
+pub struct Options {
+    /// Commits a pair must share to be reported.
+    pub min_support: u32,
+    /// How often one file of a pair must change together with the other to be reported.
+    pub min_confidence: f64,
+    /// Commits changing more files than this are skipped, since big refactorings and formatting
+    /// changes couple everything with everything.
+    pub max_commit_size: usize,
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoupledPair {
+    pub file: String,
+    pub coupled_file: String,
+    /// Commits changing both files.
+    pub support: u32,
+    pub revisions: u32,
+    pub coupled_revisions: u32,
+    /// Share of the commits to the less often changed file that also changed the other one.
+    pub confidence: f64,
+    /// Shared commits relative to the average number of commits to the two files.
+    pub degree: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CouplingStatistics {
+    pub commits_analyzed: u32,
+    pub commits_skipped: u32,
+    pub pairs: Vec<CoupledPair>,
+}
+
+#[derive(Debug, Default)]
+struct CouplingCounter {
+    revisions: HashMap<PathBuf, u32>,
+    shared: HashMap<(PathBuf, PathBuf), u32>,
+}
+
+impl CouplingCounter {
+    fn add_commit(&mut self, paths: &BTreeSet<PathBuf>) {
+        for path in paths {
+            *self.revisions.entry(path.clone()).or_insert(0) += 1;
+        }
+
+        // Paths are sorted, so every pair is only counted in one order.
+        let paths: Vec<&PathBuf> = paths.iter().collect();
+        for (index, a) in paths.iter().enumerate() {
+            for b in &paths[index + 1..] {
+                *self.shared.entry(((*a).clone(), (*b).clone())).or_insert(0) += 1;
+            }
+        }
+    }
+
+    fn into_pairs(self, options: &Options) -> Vec<CoupledPair> {
+        let revisions = self.revisions;
+
+        let mut pairs: Vec<CoupledPair> = self.shared
+            .into_iter()
+            .filter(|&(_, support)| support >= options.min_support)
+            .map(|((a, b), support)| {
+                let a_revisions = revisions[&a];
+                let b_revisions = revisions[&b];
+                CoupledPair {
+                    file: a.to_string_lossy().into_owned(),
+                    coupled_file: b.to_string_lossy().into_owned(),
+                    support: support,
+                    revisions: a_revisions,
+                    coupled_revisions: b_revisions,
+                    confidence: f64::from(support) / f64::from(a_revisions.min(b_revisions)),
+                    degree: f64::from(support) * 2.0 / f64::from(a_revisions + b_revisions),
+                }
+            })
+            .filter(|pair| pair.confidence >= options.min_confidence)
+            .collect();
+
+        // Note: Reverse sort
+        pairs.sort_by(|a, b| {
+            b.degree
+                .partial_cmp(&a.degree)
+                .unwrap_or(::std::cmp::Ordering::Equal)
+                .then_with(|| b.support.cmp(&a.support))
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.coupled_file.cmp(&b.coupled_file))
+        });
+        pairs.truncate(options.limit);
+        pairs
+    }
+}
+
+/// Finds files that tend to change in the same commits. Only files that still exist are reported,
+/// under the path they have today.
+pub fn calculate(
+    context: &Context,
+    selection: &Selection,
+    options: &Options,
+) -> Result<CouplingStatistics> {
+    let repo = context.repo();
+    let head_commit = context.head_commit()?;
+
+    let existing_files: HashSet<PathBuf> = TreeWalker::new(repo, head_commit.tree()?)
+        .filter(|entry| entry.is_file())
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+
+    let mut renames = RenameTracker::new();
+    let mut counter = CouplingCounter::default();
+    let mut commits_analyzed = 0;
+    let mut commits_skipped = 0;
+
+    for commit in CommitWalker::new(repo, &selection.without_dates())? {
+        if history::is_merge(&commit) {
+            continue;
+        }
+
+        let changes = history::changed_files(repo, &commit)?;
+        // Renames are followed even in skipped commits, or older changes would be lost.
+        let paths: BTreeSet<PathBuf> = changes
+            .iter()
+            .map(|change| renames.follow(change))
+            .filter(|path| existing_files.contains(path))
+            .collect();
+
+        if !selection.includes(&commit) {
+            continue;
+        }
+        if changes.len() > options.max_commit_size {
+            commits_skipped += 1;
+            continue;
+        }
+
+        commits_analyzed += 1;
+        counter.add_commit(&paths);
+    }
+
+    Ok(CouplingStatistics {
+        commits_analyzed: commits_analyzed,
+        commits_skipped: commits_skipped,
+        pairs: counter.into_pairs(options),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(paths: &[&str]) -> BTreeSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    fn options() -> Options {
+        Options {
+            min_support: 2,
+            min_confidence: 0.5,
+            max_commit_size: 10,
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn it_reports_files_changing_together() {
+        let mut counter = CouplingCounter::default();
+        counter.add_commit(&commit(&["a.rs", "b.rs"]));
+        counter.add_commit(&commit(&["a.rs", "b.rs", "c.rs"]));
+        counter.add_commit(&commit(&["a.rs"]));
+        counter.add_commit(&commit(&["a.rs", "c.rs"]));
+        counter.add_commit(&commit(&["c.rs"]));
+        counter.add_commit(&commit(&["c.rs"]));
+
+        let pairs = counter.into_pairs(&options());
+
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].file, "a.rs");
+        assert_eq!(pairs[0].coupled_file, "b.rs");
+        assert_eq!(pairs[0].support, 2);
+        assert_eq!(pairs[0].confidence, 1.0);
+        assert_eq!(pairs[0].degree, 2.0 * 2.0 / 6.0);
+        assert_eq!(pairs[1].coupled_file, "c.rs");
+        assert_eq!(pairs[1].confidence, 0.5);
+    }
+
+    #[test]
+    fn it_filters_by_confidence() {
+        let mut counter = CouplingCounter::default();
+        for _ in 0..2 {
+            counter.add_commit(&commit(&["a.rs", "b.rs"]));
+        }
+        for _ in 0..3 {
+            counter.add_commit(&commit(&["a.rs"]));
+            counter.add_commit(&commit(&["b.rs"]));
+        }
+
+        assert!(counter.into_pairs(&options()).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use git2::Time;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Seconds since the epoch, as seen on the wall clock of whoever made the timestamp.
+pub fn local_seconds(time: &Time) -> i64 {
+    time.seconds() + (time.offset_minutes() as i64) * 60
+}
+
+/// The number of days since 1970-01-01 in the timezone of the timestamp.
+pub fn local_day(time: &Time) -> i64 {
+    div_floor(local_seconds(time), SECONDS_PER_DAY)
+}
+
+/// Seconds since the epoch right now.
+pub fn now() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(_) => 0,
+    }
+}
+
+/// The current day number, in UTC.
+pub fn today() -> i64 {
+    div_floor(now(), SECONDS_PER_DAY)
+}
+
+/// The hour of the day (0-23) in the timezone of the timestamp.
+pub fn local_hour(time: &Time) -> usize {
+    (local_seconds(time) - local_day(time) * SECONDS_PER_DAY) as usize / 3600
+}
+
+/// The day of the week of a day number, where Monday is 0 and Sunday is 6.
+pub fn weekday(day: i64) -> usize {
+    // 1970-01-01 was a Thursday.
+    ((day % 7 + 7 + 3) % 7) as usize
+}
+
+/// Formats a day number (days since 1970-01-01) as an ISO 8601 date.
+pub fn format_day(day: i64) -> String {
+    let (year, month, day) = civil_from_days(day);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Parses an ISO 8601 date like "2024-03-01" into a day number.
+pub fn parse_day(input: &str) -> Option<i64> {
+    let parts: Vec<&str> = input.trim().split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let (year, month, day) = match (
+        parts[0].parse::<i64>(),
+        parts[1].parse::<u32>(),
+        parts[2].parse::<u32>(),
+    ) {
+        (Ok(year), Ok(month), Ok(day)) => (year, month, day),
+        _ => return None,
+    };
+
+    if month < 1 || month > 12 || day < 1 || day > 31 {
+        return None;
+    }
+
+    let day_number = days_from_civil(year, month, day);
+    if civil_from_days(day_number) == (year, month, day) {
+        Some(day_number)
+    } else {
+        None
+    }
+}
+
+/// Parses a date given on the command line: an ISO 8601 date, "today", "yesterday" or a relative
+/// date like "3 days ago", "2 weeks ago", "6 months ago" or "1 year ago".
+pub fn parse_relative_day(input: &str, today: i64) -> Option<i64> {
+    let input = input.trim().to_lowercase();
+    if let Some(day) = parse_day(&input) {
+        return Some(day);
+    }
+
+    match input.as_str() {
+        "today" => return Some(today),
+        "yesterday" => return Some(today - 1),
+        _ => {}
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    if words.len() != 3 || words[2] != "ago" {
+        return None;
+    }
+    let amount = match words[0].parse::<i64>() {
+        Ok(amount) if amount >= 0 => amount,
+        _ => return None,
+    };
+
+    match words[1].trim_right_matches('s') {
+        "day" => Some(today - amount),
+        "week" => Some(today - amount * 7),
+        "month" => Some(months_before(today, amount)),
+        "year" => Some(months_before(today, amount * 12)),
+        _ => None,
+    }
+}
+
+/// The same day of the month a number of months earlier, or the last day of that month if it is
+/// shorter.
+fn months_before(day: i64, months: i64) -> i64 {
+    let (year, month, date) = civil_from_days(day);
+    let total_months = year * 12 + (month as i64 - 1) - months;
+    let year = div_floor(total_months, 12);
+    let month = (total_months - year * 12) as u32 + 1;
+
+    days_from_civil(year, month, date.min(days_in_month(year, month)))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    let next_month = if month == 12 {
+        days_from_civil(year + 1, 1, 1)
+    } else {
+        days_from_civil(year, month + 1, 1)
+    };
+    (next_month - days_from_civil(year, month, 1)) as u32
+}
+
+/// Converts a day number into a (year, month, day) triple.
+///
+/// This is the `civil_from_days` algorithm by Howard Hinnant; see
+/// http://howardhinnant.github.io/date_algorithms.html for the details.
+pub fn civil_from_days(day: i64) -> (i64, u32, u32) {
+    let z = day + 719_468;
+    let era = div_floor(z, 146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month as u32, day as u32)
+}
+
+/// Converts a (year, month, day) triple into a day number. The inverse of `civil_from_days`.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let month = month as i64;
+    let era = div_floor(year, 400);
+    let year_of_era = year - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn div_floor(a: i64, b: i64) -> i64 {
+    let quotient = a / b;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_between_days_and_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(days_from_civil(2000, 2, 29), 11_016);
+        assert_eq!(days_from_civil(2024, 3, 1), 19_783);
+
+        for day in -1000..1000 {
+            let (year, month, date) = civil_from_days(day * 37);
+            assert_eq!(days_from_civil(year, month, date), day * 37);
+        }
+    }
+
+    #[test]
+    fn it_parses_relative_dates() {
+        let today = days_from_civil(2024, 3, 31);
+
+        assert_eq!(parse_relative_day("2024-01-15", today), Some(days_from_civil(2024, 1, 15)));
+        assert_eq!(parse_relative_day("Yesterday", today), Some(today - 1));
+        assert_eq!(parse_relative_day("2 weeks ago", today), Some(today - 14));
+        assert_eq!(parse_relative_day("1 month ago", today), Some(days_from_civil(2024, 2, 29)));
+        assert_eq!(parse_relative_day("6 months ago", today), Some(days_from_civil(2023, 9, 30)));
+        assert_eq!(parse_relative_day("1 year ago", today), Some(days_from_civil(2023, 3, 31)));
+        assert_eq!(parse_relative_day("3 fortnights ago", today), None);
+        assert_eq!(parse_relative_day("soon", today), None);
+    }
+
+    #[test]
+    fn it_parses_dates() {
+        assert_eq!(parse_day("2024-03-01"), Some(19_783));
+        assert_eq!(parse_day(" 1970-01-01 "), Some(0));
+        assert_eq!(parse_day("2023-02-29"), None);
+        assert_eq!(parse_day("2024-13-01"), None);
+        assert_eq!(parse_day("March 1st"), None);
+    }
+
+    #[test]
+    fn it_uses_the_timezone_of_the_timestamp() {
+        // 2017-10-01 23:30 UTC
+        let time = Time::new(1_506_900_600, 0);
+        assert_eq!(format_day(local_day(&time)), "2017-10-01");
+
+        let time = Time::new(1_506_900_600, 120);
+        assert_eq!(format_day(local_day(&time)), "2017-10-02");
+
+        let time = Time::new(1_506_900_600, -24 * 60);
+        assert_eq!(format_day(local_day(&time)), "2017-09-30");
+
+        let time = Time::new(1_506_900_600, 0);
+        assert_eq!(local_hour(&time), 23);
+        assert_eq!(weekday(local_day(&time)), 6);
+
+        let time = Time::new(1_506_900_600, 120);
+        assert_eq!(local_hour(&time), 1);
+        assert_eq!(weekday(local_day(&time)), 0);
+    }
+}
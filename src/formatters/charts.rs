@@ -0,0 +1,471 @@
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use activity::{Heatmap, WEEKDAYS};
+use super::text::format_count;
+use timeline::Timeline;
+
+// Charts are drawn as plain SVG strings, so they can be inlined into HTML or saved as files
+// without any extra dependencies.
+
+const FONT: &'static str = "font-family=\"sans-serif\" font-size=\"11\" fill=\"#555\"";
+const TITLE_FONT: &'static str =
+    "font-family=\"sans-serif\" font-size=\"14\" font-weight=\"bold\" fill=\"#24292e\"";
+
+/// Pie charts show this many slices; everyone else is put together in one more slice.
+const MAX_SLICES: usize = 9;
+const EVERYONE_ELSE: &'static str = "Everyone else";
+const EVERYONE_ELSE_COLOR: &'static str = "#bbb";
+
+/// Escapes text for use in XML, which covers both SVG and HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A color that only depends on the name, so the same person or team gets the same color in every
+/// chart and on every run.
+pub fn color(name: &str) -> String {
+    // 32-bit FNV-1a
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in name.bytes() {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    let hue = hash % 360;
+    let lightness = 38 + (hash / 360) % 20;
+    format!("hsl({}, 60%, {}%)", hue, lightness)
+}
+
+/// The elements of a chart, with the size they need.
+#[derive(Debug)]
+pub struct Chart {
+    pub width: f64,
+    pub height: f64,
+    body: String,
+}
+
+impl Chart {
+    pub fn to_svg(&self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\">{}</svg>",
+            self.width,
+            self.height,
+            self.width,
+            self.height,
+            self.body
+        )
+    }
+}
+
+/// Puts charts next to each other, like people and teams.
+pub fn side_by_side(charts: Vec<Chart>) -> Chart {
+    let mut body = String::new();
+    let mut width = 0.0;
+    let mut height: f64 = 0.0;
+    for chart in charts {
+        write!(body, "<g transform=\"translate({},0)\">{}</g>", width, chart.body).unwrap();
+        width += chart.width;
+        height = height.max(chart.height);
+    }
+    Chart {
+        width: width,
+        height: height,
+        body: body,
+    }
+}
+
+/// A named line in a line chart.
+#[derive(Debug)]
+pub struct Series {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+/// A named share of a pie chart.
+#[derive(Debug)]
+pub struct Slice {
+    pub name: String,
+    pub value: f64,
+}
+
+fn legend_entry(body: &mut String, x: f64, y: f64, color: &str, text: &str) {
+    write!(
+        body,
+        "<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\"/>\
+         <text x=\"{}\" y=\"{}\" {}>{}</text>",
+        x,
+        y,
+        color,
+        x + 18.0,
+        y + 10.0,
+        FONT,
+        escape(text)
+    ).unwrap();
+}
+
+/// Commits by weekday and hour, darker for busier hours.
+pub fn heatmap(heatmap: &Heatmap) -> Chart {
+    let cell = 22;
+    let left = 40;
+    let top = 20;
+    let max = heatmap.max();
+
+    let mut body = String::new();
+    for hour in (0..24).filter(|hour| hour % 3 == 0) {
+        write!(
+            body,
+            "<text x=\"{}\" y=\"{}\" {}>{:02}</text>",
+            left + hour * cell + 4,
+            top - 6,
+            FONT,
+            hour
+        ).unwrap();
+    }
+
+    for (weekday, weekday_name) in WEEKDAYS.iter().enumerate() {
+        let y = top + weekday * cell;
+        write!(
+            body,
+            "<text x=\"0\" y=\"{}\" {}>{}</text>",
+            y + cell - 7,
+            FONT,
+            &weekday_name[0..3]
+        ).unwrap();
+
+        for hour in 0..24 {
+            let commits = heatmap.get(weekday, hour);
+            let fill = if commits == 0.0 || max == 0.0 {
+                String::from("fill=\"#ebedf0\"")
+            } else {
+                let opacity = 0.15 + 0.85 * commits / max;
+                format!("fill=\"#216e39\" fill-opacity=\"{:.2}\"", opacity)
+            };
+            write!(
+                body,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"3\" {}>\
+                 <title>{} {:02}:00: {} commits</title></rect>",
+                left + hour * cell,
+                y,
+                cell - 2,
+                cell - 2,
+                fill,
+                weekday_name,
+                hour,
+                format_count(commits)
+            ).unwrap();
+        }
+    }
+
+    Chart {
+        width: (left + 24 * cell) as f64,
+        height: (top + 7 * cell) as f64,
+        body: body,
+    }
+}
+
+/// One line per series over the labels of the x axis, with a legend below the chart.
+pub fn line_chart(labels: &[String], series: &[Series]) -> Chart {
+    let width = 760.0;
+    let plot_height = 220.0;
+    let left = 44.0;
+    let right = 12.0;
+    let top = 10.0;
+    let bottom = 24.0;
+    let legend_line = 18.0;
+    let plot_width = width - left - right;
+
+    let max = series
+        .iter()
+        .flat_map(|series| series.values.iter())
+        .cloned()
+        .fold(0.0, f64::max)
+        .ceil()
+        .max(1.0);
+    let x = |index: usize| if labels.len() > 1 {
+        left + plot_width * index as f64 / (labels.len() - 1) as f64
+    } else {
+        left + plot_width / 2.0
+    };
+    let y = |value: f64| top + plot_height - plot_height * value / max;
+
+    let mut body = String::new();
+    for value in &[0.0, max / 2.0, max] {
+        write!(
+            body,
+            "<line x1=\"{}\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"#ddd\"/>\
+             <text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\" {}>{}</text>",
+            left,
+            y(*value),
+            width - right,
+            y(*value),
+            left - 6.0,
+            y(*value) + 4.0,
+            FONT,
+            format_count(*value)
+        ).unwrap();
+    }
+
+    // Keep the labels on the x axis readable by showing no more than about a dozen of them.
+    let step = (labels.len() + 11) / 12;
+    for (index, label) in labels.iter().enumerate() {
+        if index % step.max(1) == 0 || index + 1 == labels.len() {
+            write!(
+                body,
+                "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\" {}>{}</text>",
+                x(index),
+                top + plot_height + 16.0,
+                FONT,
+                escape(label)
+            ).unwrap();
+        }
+    }
+
+    for (position, series) in series.iter().enumerate() {
+        let color = color(&series.name);
+        let points: Vec<String> = series
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| format!("{:.1},{:.1}", x(index), y(*value)))
+            .collect();
+        write!(
+            body,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\">\
+             <title>{}</title></polyline>",
+            points.join(" "),
+            color,
+            escape(&series.name)
+        ).unwrap();
+        // Points make single values visible, and tell the exact numbers on hover.
+        for (index, value) in series.values.iter().enumerate() {
+            write!(
+                body,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\">\
+                 <title>{}, {}: {}</title></circle>",
+                x(index),
+                y(*value),
+                color,
+                escape(&series.name),
+                labels.get(index).map(|label| escape(label)).unwrap_or_default(),
+                format_count(*value)
+            ).unwrap();
+        }
+
+        let legend_y = top + plot_height + bottom + legend_line * position as f64;
+        legend_entry(&mut body, left, legend_y, &color, &series.name);
+    }
+
+    Chart {
+        width: width,
+        height: top + plot_height + bottom + legend_line * series.len() as f64,
+        body: body,
+    }
+}
+
+/// Keeps the biggest slices and puts the rest together, so the chart stays readable.
+fn biggest_slices(mut slices: Vec<Slice>) -> Vec<(Slice, String)> {
+    slices.retain(|slice| slice.value > 0.0);
+    let rest: f64 = slices.iter().skip(MAX_SLICES).map(|slice| slice.value).sum();
+    slices.truncate(MAX_SLICES);
+
+    let mut colored: Vec<(Slice, String)> = slices
+        .into_iter()
+        .map(|slice| {
+            let color = color(&slice.name);
+            (slice, color)
+        })
+        .collect();
+    if rest > 0.0 {
+        colored.push((
+            Slice {
+                name: String::from(EVERYONE_ELSE),
+                value: rest,
+            },
+            String::from(EVERYONE_ELSE_COLOR),
+        ));
+    }
+    colored
+}
+
+/// A pie chart of the slices, biggest first, with a legend next to it.
+pub fn pie_chart(title: &str, slices: Vec<Slice>) -> Chart {
+    let radius = 100.0;
+    let center_x = 110.0;
+    let center_y = 140.0;
+    let legend_x = 230.0;
+    let legend_top = 40.0;
+    let legend_line = 18.0;
+
+    let slices = biggest_slices(slices);
+    let total: f64 = slices.iter().map(|&(ref slice, _)| slice.value).sum();
+
+    let mut body = String::new();
+    write!(body, "<text x=\"0\" y=\"16\" {}>{}</text>", TITLE_FONT, escape(title)).unwrap();
+
+    if slices.is_empty() {
+        write!(
+            body,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" {}>Nothing to show</text>",
+            center_x,
+            center_y,
+            FONT
+        ).unwrap();
+    }
+
+    // Slices go clockwise, starting at the top.
+    let point = |angle: f64| {
+        (center_x + radius * angle.sin(), center_y - radius * angle.cos())
+    };
+    let mut angle = 0.0;
+    for (position, &(ref slice, ref color)) in slices.iter().enumerate() {
+        let fraction = slice.value / total;
+        let tooltip = format!(
+            "<title>{}: {} ({:.1}%)</title>",
+            escape(&slice.name),
+            format_count(slice.value),
+            fraction * 100.0
+        );
+
+        if slices.len() == 1 {
+            write!(
+                body,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\">{}</circle>",
+                center_x,
+                center_y,
+                radius,
+                color,
+                tooltip
+            ).unwrap();
+        } else {
+            let (start_x, start_y) = point(angle);
+            angle += fraction * 2.0 * PI;
+            let (end_x, end_y) = point(angle);
+            write!(
+                body,
+                "<path d=\"M{},{} L{:.2},{:.2} A{},{} 0 {},1 {:.2},{:.2} Z\" fill=\"{}\" \
+                 stroke=\"#fff\">{}</path>",
+                center_x,
+                center_y,
+                start_x,
+                start_y,
+                radius,
+                radius,
+                if fraction > 0.5 { 1 } else { 0 },
+                end_x,
+                end_y,
+                color,
+                tooltip
+            ).unwrap();
+        }
+
+        let legend_y = legend_top + legend_line * position as f64;
+        let text = format!("{} ({:.1}%)", slice.name, fraction * 100.0);
+        legend_entry(&mut body, legend_x, legend_y, color, &text);
+    }
+
+    Chart {
+        width: 460.0,
+        height: (center_y + radius + 10.0).max(legend_top + legend_line * slices.len() as f64),
+        body: body,
+    }
+}
+
+/// Monthly commits of the repository and of the busiest teams. Without any teams in the config,
+/// the busiest people are shown instead.
+pub fn timeline(timeline: &Timeline) -> Chart {
+    let months = timeline.months();
+
+    let mut series = vec![
+        Series {
+            name: String::from("All commits"),
+            values: timeline.repository.series(&months),
+        },
+    ];
+
+    let teams: Vec<Series> = timeline
+        .teams_toplist()
+        .into_iter()
+        .filter_map(|(team_name, commits)| {
+            team_name.map(|name| {
+                Series {
+                    name: name.to_owned(),
+                    values: commits.series(&months),
+                }
+            })
+        })
+        .take(5)
+        .collect();
+
+    if teams.is_empty() {
+        series.extend(timeline.people_toplist().into_iter().take(5).map(
+            |(person, commits)| {
+                Series {
+                    name: person.name().to_owned(),
+                    values: commits.series(&months),
+                }
+            },
+        ));
+    } else {
+        series.extend(teams);
+    }
+
+    let labels: Vec<String> = months.iter().map(|month| month.to_string()).collect();
+    line_chart(&labels, &series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice(name: &str, value: f64) -> Slice {
+        Slice {
+            name: name.to_owned(),
+            value: value,
+        }
+    }
+
+    #[test]
+    fn it_gives_names_stable_colors() {
+        assert_eq!(color("Backend"), color("Backend"));
+        assert_ne!(color("Backend"), color("Frontend"));
+        assert_eq!(color(""), "hsl(61, 60%, 43%)");
+    }
+
+    #[test]
+    fn it_puts_small_slices_together() {
+        let mut slices: Vec<Slice> = (0..12).map(|i| slice(&i.to_string(), 10.0)).collect();
+        slices.push(slice("Nobody", 0.0));
+
+        let biggest = biggest_slices(slices);
+        assert_eq!(biggest.len(), MAX_SLICES + 1);
+        assert_eq!(biggest[0].1, color("0"));
+
+        let &(ref rest, ref rest_color) = biggest.last().unwrap();
+        assert_eq!(rest.name, EVERYONE_ELSE);
+        assert_eq!(rest.value, 30.0);
+        assert_eq!(rest_color, EVERYONE_ELSE_COLOR);
+    }
+
+    #[test]
+    fn it_rounds_the_values_it_shows() {
+        let third = 100.0 / 3.0;
+
+        let pie = pie_chart("Lines", vec![slice("Backend", third), slice("Frontend", 2.0 * third)]);
+        assert!(pie.body.contains("<title>Backend: 33.33 (33.3%)</title>"));
+
+        let series = vec![
+            Series {
+                name: String::from("Backend"),
+                values: vec![third, 3.0],
+            },
+        ];
+        let line = line_chart(&[String::from("2018-01"), String::from("2018-02")], &series);
+        assert!(line.body.contains("<title>Backend, 2018-01: 33.33</title>"));
+        assert!(line.body.contains(">17</text>"));
+        assert!(!line.body.contains("33.333"));
+    }
+}
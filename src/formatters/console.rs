@@ -3,10 +3,32 @@ extern crate term;
 use std::io::prelude::*;
 
 use prettytable::Table;
+use prettytable::row::Row;
 use term::{Attr, color};
 
 use ownership::OwnershipStatistics;
+use commits::{CommitStatistics, ComputedCommitActivity};
+use activity::{ActivityStatistics, Heatmap, WEEKDAYS};
+use streaks::{StreakStatistics, ComputedStreaks};
+use churn::{ChurnStatistics, ComputedChurn};
+use hotspots::HotspotStatistics;
+use truck_factor::TruckFactorReport;
+use knowledge_loss::{KnowledgeLossReport, LossShare};
+use codeowners::CodeOwners;
+use codeowners_audit::CodeOwnersAudit;
+use line_age::{LineAgeStatistics, ComputedLineAges};
+use collaboration::CollaborationGraph;
+use coupling::CouplingStatistics;
+use messages::{MessageStatistics, ComputedMessages, Subject};
+use commit_types::{CommitTypeStatistics, ComputedTypes};
+use release_notes::{ReleaseNotes, ComputedContribution};
+use newcomers::NewcomerReport;
+use timeline::{Timeline, MonthlyCommits};
+use dates;
+use person::{Person, NO_TEAM_NAME};
 use errors::*;
+use super::text::{format_age, format_busiest_month, format_count, format_lines_owned,
+                  format_net_lines, format_other_types, format_span, people_names, team_names};
 
 fn new_table() -> Table {
     use prettytable::format;
@@ -175,7 +197,7 @@ impl<'a, 'b> Format for &'a OwnershipStatistics<'b> {
         for (index, &(person, ref score)) in self.people_toplist().iter().enumerate() {
             let place = (index + 1).to_string();
             let name = person.name();
-            let lines = score.total_lines_owned.to_string();
+            let lines = format_lines_owned(score);
             let percent = format!("{:6.2}%", score.percent_owned());
 
             people_table.add_row(row![place, name, lines, percent]);
@@ -192,15 +214,1077 @@ impl<'a, 'b> Format for &'a OwnershipStatistics<'b> {
             let place = (index + 1).to_string();
             let name = match *team_name {
                 Some(name) => name,
-                None => "(Others)",
+                None => NO_TEAM_NAME,
             };
-            let lines = score.total_lines_owned.to_string();
+            let lines = format_lines_owned(score);
             let percent = format!("{:6.2}%", score.percent_owned());
 
             teams_table.add_row(row![place, name, lines, percent]);
         }
         teams_table.printstd();
 
+        for note in self.notes() {
+            writeln!(terminal, "{}", note)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn commit_activity_row(place: usize, name: &str, activity: &ComputedCommitActivity) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(activity.commits),
+        activity.first_commit.as_ref().map(String::as_str).unwrap_or("-"),
+        activity.last_commit.as_ref().map(String::as_str).unwrap_or("-"),
+        activity.active_days.to_string(),
+        format!("{:.2}", activity.commits_per_active_day)
+    ]
+}
+
+impl<'a, 'b> Format for &'a CommitStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Commit activity")?;
+
+        let repository = self.repository_activity();
+        terminal.print_fact("Total commits", format_count(repository.commits))?;
+        if let Some(ref first_commit) = repository.first_commit {
+            terminal.print_fact("First commit", first_commit)?;
+        }
+        if let Some(ref last_commit) = repository.last_commit {
+            terminal.print_fact("Last commit", last_commit)?;
+        }
+        terminal.print_fact("Active days", repository.active_days)?;
+        terminal.print_fact(
+            "Commits per active day",
+            format!("{:.2}", repository.commits_per_active_day),
+        )?;
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = new_table();
+        people_table.add_row(row![
+            b->"#",
+            b->"Person",
+            b->"Commits",
+            b->"First commit",
+            b->"Last commit",
+            b->"Active days",
+            b->"Commits per day"
+        ]);
+
+        for (index, &(person, ref activity)) in self.people_toplist().iter().enumerate() {
+            people_table.add_row(commit_activity_row(index + 1, person.name(), activity));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = new_table();
+        teams_table.add_row(row![
+            b->"#",
+            b->"Team",
+            b->"Commits",
+            b->"First commit",
+            b->"Last commit",
+            b->"Active days",
+            b->"Commits per day"
+        ]);
+
+        for (index, &(ref team_name, ref activity)) in self.teams_toplist().iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(commit_activity_row(index + 1, name, activity));
+        }
+        teams_table.printstd();
+
+        Ok(())
+    }
+}
+
+fn print_heatmap(terminal: &mut Terminal, heatmap: &Heatmap) -> Result<()> {
+    // Shades used for cells, from no commits at all up to the busiest hour of the week.
+    let colors = [color::BLUE, color::GREEN, color::BRIGHT_GREEN, color::YELLOW, color::BRIGHT_RED];
+    let symbols = [" ", ".", "-", "+", "#"];
+    let max = heatmap.max();
+
+    write!(terminal, "    ")?;
+    for hour in 0..24 {
+        write!(terminal, "{:02}", hour)?;
+    }
+    writeln!(terminal, "")?;
+
+    for (weekday, weekday_name) in WEEKDAYS.iter().enumerate() {
+        write!(terminal, "{:<4}", &weekday_name[0..3])?;
+        for hour in 0..24 {
+            let count = heatmap.get(weekday, hour);
+            let level = if count == 0.0 || max == 0.0 {
+                0
+            } else {
+                1 + ((count * 4.0 / max) as usize).min(3)
+            };
+
+            if terminal.is_term() {
+                terminal.attr(Attr::BackgroundColor(colors[level]))?;
+                write!(terminal, "  ")?;
+                terminal.reset()?;
+            } else {
+                write!(terminal, "{0}{0}", symbols[level])?;
+            }
+        }
+        writeln!(terminal, " {}", format_count(heatmap.on_weekday(weekday)))?;
+    }
+
+    Ok(())
+}
+
+impl<'a, 'b> Format for &'a ActivityStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Activity")?;
+
+        terminal.print_fact("Total commits", format_count(self.repository.total()))?;
+        if let Some(weekday) = self.repository.busiest_weekday() {
+            terminal.print_fact("Busiest day", WEEKDAYS[weekday])?;
+        }
+        if let Some(hour) = self.repository.busiest_hour() {
+            terminal.print_fact("Busiest hour", format!("{:02}:00-{:02}:59", hour, hour))?;
+        }
+        if let Some((person, commits)) = self.night_owl() {
+            terminal.print_fact(
+                "Night owl",
+                format!(
+                    "{} ({} commits between 00:00 and 06:00)",
+                    person.name(),
+                    format_count(commits)
+                ),
+            )?;
+        }
+        if let Some((team_name, commits)) = self.friday_team() {
+            terminal.print_fact(
+                "Ships on Fridays",
+                format!("{} ({} commits)", team_name.unwrap_or(NO_TEAM_NAME), format_count(commits)),
+            )?;
+        }
+
+        terminal.print_headline("\nRepository")?;
+        print_heatmap(terminal, &self.repository)?;
+
+        for (team_name, heatmap) in self.teams_toplist() {
+            if heatmap.total() > 0.0 {
+                terminal.print_headline(&format!("\nTeam: {}", team_name.unwrap_or(NO_TEAM_NAME)))?;
+                print_heatmap(terminal, heatmap)?;
+            }
+        }
+
+        for (person, heatmap) in self.people_toplist() {
+            terminal.print_headline(&format!("\nPerson: {}", person.name()))?;
+            print_heatmap(terminal, heatmap)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn streaks_row(place: usize, name: &str, streaks: &ComputedStreaks) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_span(&streaks.longest_streak),
+        streaks.current_streak.to_string(),
+        format_span(&streaks.longest_gap)
+    ]
+}
+
+impl<'a, 'b> Format for &'a StreakStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Streaks")?;
+
+        if let Some((person, streak)) = self.longest_streak_by_anyone() {
+            terminal.print_fact(
+                "Longest streak by anyone",
+                format!("{} by {}", format_span(&Some(streak)), person.name()),
+            )?;
+        }
+        if let Some((person, days)) = self.longest_current_streak() {
+            terminal.print_fact(
+                "Longest current streak",
+                format!("{} days by {}", days, person.name()),
+            )?;
+        }
+
+        let repository = self.repository_streaks();
+        terminal.print_fact(
+            "Longest streak of the repository",
+            format_span(&repository.longest_streak),
+        )?;
+        terminal.print_fact(
+            "Longest time the repository went untouched",
+            format_span(&repository.longest_gap),
+        )?;
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = new_table();
+        people_table.add_row(row![
+            b->"#",
+            b->"Person",
+            b->"Longest streak",
+            b->"Current streak",
+            b->"Longest gap"
+        ]);
+
+        for (index, &(person, ref streaks)) in self.people_toplist().iter().enumerate() {
+            people_table.add_row(streaks_row(index + 1, person.name(), streaks));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = new_table();
+        teams_table.add_row(row![
+            b->"#",
+            b->"Team",
+            b->"Longest streak",
+            b->"Current streak",
+            b->"Longest gap"
+        ]);
+
+        for (index, &(ref team_name, ref streaks)) in self.teams_toplist().iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(streaks_row(index + 1, name, streaks));
+        }
+        teams_table.printstd();
+
+        Ok(())
+    }
+}
+
+fn churn_row(place: usize, name: &str, churn: &ComputedChurn) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(churn.commits),
+        format_count(churn.insertions),
+        format_count(churn.deletions),
+        format_net_lines(churn.net_lines),
+        churn.files_touched.to_string()
+    ]
+}
+
+fn churn_table(first_column: &str) -> Table {
+    let mut table = new_table();
+    table.add_row(row![
+        b->"#",
+        b->first_column,
+        b->"Commits",
+        b->"Added",
+        b->"Removed",
+        b->"Net lines",
+        b->"Files touched"
+    ]);
+    table
+}
+
+impl<'a, 'b> Format for &'a ChurnStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Churn")?;
+
+        let repository = self.repository_churn();
+        terminal.print_fact("Commits", format_count(repository.commits))?;
+        terminal.print_fact("Lines added", format_count(repository.insertions))?;
+        terminal.print_fact("Lines removed", format_count(repository.deletions))?;
+        terminal.print_fact("Net lines", format_net_lines(repository.net_lines))?;
+        terminal.print_fact("Files touched", repository.files_touched)?;
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = churn_table("Person");
+        for (index, &(person, ref churn)) in self.people_toplist().iter().enumerate() {
+            people_table.add_row(churn_row(index + 1, person.name(), churn));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = churn_table("Team");
+        for (index, &(ref team_name, ref churn)) in self.teams_toplist().iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(churn_row(index + 1, name, churn));
+        }
+        teams_table.printstd();
+
+        terminal.print_headline("\nNet lines leaderboard")?;
+        let mut leaderboard_table = new_table();
+        leaderboard_table.add_row(row![b->"#", b->"Person", b->"Net lines"]);
+        for (index, &(person, ref churn)) in self.net_lines_leaderboard().iter().enumerate() {
+            leaderboard_table.add_row(row![
+                (index + 1).to_string(),
+                person.name(),
+                format_net_lines(churn.net_lines)
+            ]);
+        }
+        leaderboard_table.printstd();
+
+        terminal.print_headline("\nBiggest commits")?;
+        let mut commits_table = new_table();
+        commits_table.add_row(
+            row![b->"#", b->"Commit", b->"Person", b->"Added", b->"Removed", b->"Summary"],
+        );
+        for (index, commit) in self.biggest_commits.iter().enumerate() {
+            commits_table.add_row(row![
+                (index + 1).to_string(),
+                &commit.sha[0..8],
+                commit.person.name(),
+                commit.insertions.to_string(),
+                commit.deletions.to_string(),
+                commit.summary
+            ]);
+        }
+        commits_table.printstd();
+
+        Ok(())
+    }
+}
+
+impl<'a> Format for &'a HotspotStatistics {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Hotspots")?;
+
+        let mut table = new_table();
+        table.add_row(
+            row![b->"#", b->"File", b->"Commits", b->"Authors", b->"Lines", b->"Score"],
+        );
+
+        for (index, hotspot) in self.files.iter().enumerate() {
+            table.add_row(row![
+                (index + 1).to_string(),
+                hotspot.path,
+                hotspot.commits.to_string(),
+                hotspot.authors.to_string(),
+                hotspot.lines.to_string(),
+                hotspot.score.to_string()
+            ]);
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Format for &'a TruckFactorReport<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Truck factor")?;
+
+        terminal.print_fact("Files", self.people.files)?;
+        terminal.print_fact("Truck factor", self.people.factor())?;
+        terminal.print_fact("Critical people", people_names(&self.people.critical))?;
+        terminal.print_fact("Team truck factor", self.teams.factor())?;
+        terminal.print_fact("Critical teams", team_names(&self.teams.critical))?;
+
+        terminal.print_headline("\nDirectories")?;
+        let mut table = new_table();
+        table.add_row(row![
+            b->"Directory",
+            b->"Files",
+            b->"Truck factor",
+            b->"Critical people",
+            b->"Team truck factor",
+            b->"Critical teams"
+        ]);
+
+        for directory in &self.directories {
+            table.add_row(row![
+                directory.path,
+                directory.people.files.to_string(),
+                directory.people.factor().to_string(),
+                people_names(&directory.people.critical),
+                directory.teams.factor().to_string(),
+                team_names(&directory.teams.critical)
+            ]);
+        }
+        table.printstd();
+
+        writeln!(
+            terminal,
+            "People owning at least {:.0}% of a file, or most of it, are counted as its owners.",
+            self.owner_threshold * 100.0
+        )?;
+
+        Ok(())
+    }
+}
+
+fn loss_row(name: &str, loss: &LossShare) -> Row {
+    row![
+        name,
+        format!("{:.0}", loss.total_lines),
+        format!("{:.0}", loss.departed_lines),
+        format!("{:6.2}%", loss.fraction_departed() * 100.0)
+    ]
+}
+
+impl<'a, 'b> Format for &'a KnowledgeLossReport<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Knowledge loss")?;
+
+        terminal.print_fact("Departed people owning code", self.departed.len())?;
+        terminal.print_fact(
+            "Lines owned by departed people",
+            format!(
+                "{:.0} of {:.0} ({:.2}%)",
+                self.repository.departed_lines,
+                self.repository.total_lines,
+                self.repository.fraction_departed() * 100.0
+            ),
+        )?;
+        terminal.print_fact("Orphaned files", self.orphaned_files.len())?;
+
+        terminal.print_headline("\nDeparted people")?;
+        let mut people_table = new_table();
+        people_table.add_row(row![b->"Person", b->"Left", b->"Lines still owned"]);
+        for departed in &self.departed {
+            people_table.add_row(row![
+                departed.person.name(),
+                departed.person.left().unwrap_or("-"),
+                departed.lines.to_string()
+            ]);
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nDirectories")?;
+        let mut directories_table = new_table();
+        directories_table.add_row(
+            row![b->"Directory", b->"Lines", b->"Departed lines", b->"Departed share"],
+        );
+        for directory in &self.directories {
+            directories_table.add_row(loss_row(&directory.path, &directory.loss));
+        }
+        directories_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = new_table();
+        teams_table.add_row(row![b->"Team", b->"Lines", b->"Departed lines", b->"Departed share"]);
+        for &(ref team_name, ref loss) in &self.teams {
+            teams_table.add_row(loss_row(team_name.unwrap_or(NO_TEAM_NAME), loss));
+        }
+        teams_table.printstd();
+
+        terminal.print_headline("\nOrphaned files")?;
+        let mut files_table = new_table();
+        files_table.add_row(
+            row![b->"File", b->"Lines", b->"Departed lines", b->"Former owners"],
+        );
+        for file in &self.orphaned_files {
+            files_table.add_row(row![
+                file.path,
+                file.lines.to_string(),
+                file.departed_lines.to_string(),
+                people_names(&file.former_owners)
+            ]);
+        }
+        files_table.printstd();
+
+        writeln!(
+            terminal,
+            "Files where more than {:.0}% of the lines are owned by departed people are orphaned.",
+            self.threshold * 100.0
+        )?;
+
+        Ok(())
+    }
+}
+
+impl<'a> Format for &'a CodeOwners {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        write!(terminal, "{}", self.to_file_contents())?;
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Format for &'a CodeOwnersAudit<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let weak_rules = self.weak_rules();
+        let unused_rules = self.unused_rules();
+
+        terminal.print_header("CODEOWNERS audit")?;
+
+        terminal.print_fact("File", &self.path)?;
+        terminal.print_fact("Rules", self.rules.len())?;
+        terminal.print_fact("Rules with owners that own almost nothing", weak_rules.len())?;
+        terminal.print_fact("Rules matching no files", unused_rules.len())?;
+        terminal.print_fact("Files without a rule", self.unowned_files.len())?;
+        terminal.print_fact("Big owners listed nowhere", self.unlisted_owners.len())?;
+
+        terminal.print_headline("\nRules with owners that own almost nothing")?;
+        let mut rules_table = new_table();
+        rules_table.add_row(row![
+            b->"Line",
+            b->"Pattern",
+            b->"Listed owners",
+            b->"Files",
+            b->"Owned by listed",
+            b->"Actual owners"
+        ]);
+        for rule in weak_rules {
+            let mut listed = rule.rule.owners.join(", ");
+            if !rule.unknown_owners.is_empty() {
+                listed.push_str(&format!(" (unknown: {})", rule.unknown_owners.join(", ")));
+            }
+            let actual: Vec<&Person> = rule.actual_owners
+                .iter()
+                .map(|&(person, _)| person)
+                .collect();
+            rules_table.add_row(row![
+                rule.line.to_string(),
+                rule.rule.pattern,
+                listed,
+                rule.files.to_string(),
+                format!("{:.2}%", rule.fraction_owned_by_listed() * 100.0),
+                people_names(&actual)
+            ]);
+        }
+        rules_table.printstd();
+
+        terminal.print_headline("\nRules matching no files")?;
+        let mut unused_table = new_table();
+        unused_table.add_row(row![b->"Line", b->"Pattern", b->"Listed owners"]);
+        for rule in unused_rules {
+            unused_table.add_row(row![
+                rule.line.to_string(),
+                rule.rule.pattern,
+                rule.rule.owners.join(", ")
+            ]);
+        }
+        unused_table.printstd();
+
+        terminal.print_headline("\nFiles without a rule")?;
+        for path in &self.unowned_files {
+            writeln!(terminal, "{}", path)?;
+        }
+
+        terminal.print_headline("\nBig owners listed nowhere")?;
+        let mut people_table = new_table();
+        people_table.add_row(row![b->"Person", b->"Lines owned", b->"Share of repository"]);
+        for owner in &self.unlisted_owners {
+            people_table.add_row(row![
+                owner.person.name(),
+                owner.lines.to_string(),
+                format!("{:.2}%", owner.fraction_owned * 100.0)
+            ]);
+        }
+        people_table.printstd();
+
+        writeln!(
+            terminal,
+            "Rules are weak when their listed owners own less than {:.0}% of the lines they match.",
+            self.threshold * 100.0
+        )?;
+
+        Ok(())
+    }
+}
+
+fn line_age_row(name: &str, ages: &ComputedLineAges) -> Row {
+    let age = |percent| {
+        ages.percentiles
+            .iter()
+            .find(|percentile| percentile.percent == percent)
+            .map(|percentile| format_age(percentile.age_days))
+            .unwrap_or_else(|| String::from("-"))
+    };
+    row![
+        name,
+        format_count(ages.lines),
+        age(10),
+        age(25),
+        age(50),
+        age(75),
+        age(90)
+    ]
+}
+
+fn line_age_table(first_column: &str) -> Table {
+    let mut table = new_table();
+    table.add_row(row![
+        b->first_column,
+        b->"Lines",
+        b->"10%",
+        b->"25%",
+        b->"Median",
+        b->"75%",
+        b->"90%"
+    ]);
+    table
+}
+
+impl<'a, 'b> Format for &'a LineAgeStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let repository = self.repository_ages();
+
+        terminal.print_header("Line age")?;
+
+        terminal.print_fact("Surviving lines", repository.lines)?;
+        terminal.print_fact(
+            "Median age",
+            repository.median_age_days.map(format_age).unwrap_or_else(
+                || String::from("-"),
+            ),
+        )?;
+        if let Some(ref oldest) = self.oldest_line {
+            terminal.print_fact(
+                "Oldest surviving line",
+                format!(
+                    "{}:{}, last changed {} by {} in {:.7}",
+                    oldest.path,
+                    oldest.line_number,
+                    dates::format_day(oldest.day),
+                    oldest.person.name(),
+                    oldest.commit.to_string()
+                ),
+            )?;
+            writeln!(terminal, "    {}", oldest.content)?;
+        }
+
+        terminal.print_headline("\nLines per year")?;
+        let max_lines = repository
+            .lines_per_year
+            .values()
+            .fold(0.0, |max: f64, lines| max.max(*lines));
+        for (year, lines) in &repository.lines_per_year {
+            let width = if max_lines > 0.0 {
+                (lines * 40.0 / max_lines).ceil() as usize
+            } else {
+                0
+            };
+            writeln!(terminal, "{} {:>8} {}", year, format_count(*lines), "█".repeat(width))?;
+        }
+
+        terminal.print_headline("\nDirectories")?;
+        let mut directories_table = line_age_table("Directory");
+        for (path, ages) in self.directories() {
+            directories_table.add_row(line_age_row(path, &ages));
+        }
+        directories_table.printstd();
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = line_age_table("Name");
+        for (person, ages) in self.people_toplist() {
+            people_table.add_row(line_age_row(person.name(), &ages));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = line_age_table("Team");
+        for (team_name, ages) in self.teams_toplist() {
+            teams_table.add_row(line_age_row(team_name.unwrap_or(NO_TEAM_NAME), &ages));
+        }
+        teams_table.printstd();
+
+        writeln!(terminal, "Ages are counted from the last change to each line.")?;
+
+        Ok(())
+    }
+}
+
+impl<'a> Format for &'a CollaborationGraph {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let shown_edges = 20;
+
+        terminal.print_header("Collaboration")?;
+
+        terminal.print_fact(if self.teams { "Teams" } else { "People" }, self.nodes.len())?;
+        terminal.print_fact("Connections", self.edges.len())?;
+        terminal.print_fact("Time window", format!("{} days", self.window_days))?;
+
+        terminal.print_headline("\nStrongest connections")?;
+        let mut table = new_table();
+        table.add_row(row![
+            b->"#",
+            b->"Between",
+            b->"And",
+            b->"Shared files",
+            b->"Co-authored commits"
+        ]);
+        for (index, edge) in self.edges.iter().take(shown_edges).enumerate() {
+            table.add_row(row![
+                (index + 1).to_string(),
+                edge.from,
+                edge.to,
+                format_count(edge.shared_files),
+                format_count(edge.co_authored_commits)
+            ]);
+        }
+        table.printstd();
+
+        if self.edges.len() > shown_edges {
+            writeln!(
+                terminal,
+                "Showing the {} strongest of {} connections. Use --dot for the whole graph.",
+                shown_edges,
+                self.edges.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Format for &'a CouplingStatistics {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Temporal coupling")?;
+
+        terminal.print_fact("Commits analyzed", self.commits_analyzed)?;
+        terminal.print_fact("Big commits skipped", self.commits_skipped)?;
+
+        terminal.print_headline("\nFiles changing together")?;
+        let mut table = new_table();
+        table.add_row(row![
+            b->"#",
+            b->"File",
+            b->"Coupled file",
+            b->"Shared commits",
+            b->"Confidence",
+            b->"Degree"
+        ]);
+        for (index, pair) in self.pairs.iter().enumerate() {
+            table.add_row(row![
+                (index + 1).to_string(),
+                pair.file,
+                pair.coupled_file,
+                format!("{} of {}/{}", pair.support, pair.revisions, pair.coupled_revisions),
+                format!("{:.0}%", pair.confidence * 100.0),
+                format!("{:.0}%", pair.degree * 100.0)
+            ]);
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
+
+fn subjects_table(subjects: &[Subject]) -> Table {
+    let mut table = new_table();
+    table.add_row(row![b->"Length", b->"Subject", b->"Author", b->"Commit"]);
+    for subject in subjects {
+        table.add_row(row![
+            subject.length().to_string(),
+            subject.subject,
+            subject.person.name(),
+            subject.sha[0..7]
+        ]);
+    }
+    table
+}
+
+fn messages_row(place: usize, name: &str, messages: &ComputedMessages) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(messages.commits),
+        format!("{:.0}%", messages.conventional_share * 100.0),
+        format!("{:.1}", messages.average_subject_length),
+        format_count(messages.emoji),
+        messages.favorite_emoji.as_ref().map(String::as_str).unwrap_or("-"),
+        messages.most_used_word.as_ref().map(String::as_str).unwrap_or("-")
+    ]
+}
+
+fn messages_table() -> Table {
+    let mut table = new_table();
+    table.add_row(row![
+        b->"#",
+        b->"Name",
+        b->"Commits",
+        b->"Conventional",
+        b->"Avg. subject",
+        b->"Emoji",
+        b->"Favorite emoji",
+        b->"Most used word"
+    ]);
+    table
+}
+
+impl<'a, 'b> Format for &'a MessageStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let repository = self.repository_messages();
+
+        terminal.print_header("Commit messages")?;
+
+        terminal.print_fact("Commits", format_count(repository.commits))?;
+        terminal.print_fact(
+            "Following Conventional Commits",
+            format!(
+                "{} ({:.2}%)",
+                format_count(repository.conventional_commits),
+                repository.conventional_share * 100.0
+            ),
+        )?;
+        terminal.print_fact(
+            "Average subject length",
+            format!("{:.1} characters", repository.average_subject_length),
+        )?;
+        terminal.print_fact("Emoji used", format_count(repository.emoji))?;
+        if let Some(ref emoji) = repository.favorite_emoji {
+            terminal.print_fact("Favorite emoji", emoji)?;
+        }
+
+        terminal.print_headline("\nLongest subjects")?;
+        subjects_table(&self.longest_subjects).printstd();
+
+        terminal.print_headline("\nShortest subjects")?;
+        subjects_table(&self.shortest_subjects).printstd();
+
+        terminal.print_headline("\nMost common first words")?;
+        let mut words_table = new_table();
+        words_table.add_row(row![b->"Word", b->"Subjects"]);
+        for word in &repository.first_words {
+            words_table.add_row(row![word.word, format_count(word.count)]);
+        }
+        words_table.printstd();
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = messages_table();
+        for (index, (person, messages)) in self.people_toplist().into_iter().enumerate() {
+            people_table.add_row(messages_row(index + 1, person.name(), &messages));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = messages_table();
+        for (index, (team_name, messages)) in self.teams_toplist().into_iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(messages_row(index + 1, name, &messages));
+        }
+        teams_table.printstd();
+
+        Ok(())
+    }
+}
+
+fn types_row(place: usize, name: &str, types: &ComputedTypes) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(types.commits),
+        format_count(types.count("feat")),
+        format_count(types.count("fix")),
+        format_other_types(types),
+        format_count(types.breaking_changes)
+    ]
+}
+
+fn types_table() -> Table {
+    let mut table = new_table();
+    table.add_row(row![
+        b->"#",
+        b->"Name",
+        b->"Commits",
+        b->"Features",
+        b->"Fixes",
+        b->"Other types",
+        b->"Breaking"
+    ]);
+    table
+}
+
+impl<'a, 'b> Format for &'a CommitTypeStatistics<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let repository = self.repository_types();
+
+        terminal.print_header("Commit types")?;
+
+        terminal.print_fact("Commits", format_count(repository.commits))?;
+        terminal.print_fact("Features", format_count(repository.count("feat")))?;
+        terminal.print_fact("Fixes", format_count(repository.count("fix")))?;
+        terminal.print_fact("Other types", format_other_types(&repository))?;
+        terminal.print_fact("Breaking changes", format_count(repository.breaking_changes))?;
+
+        terminal.print_headline("\nBreaking changes by scope")?;
+        let mut scopes_table = new_table();
+        scopes_table.add_row(row![b->"Scope", b->"Breaking changes"]);
+        for (scope, count) in &self.breaking_changes_by_scope {
+            scopes_table.add_row(row![scope, count.to_string()]);
+        }
+        scopes_table.printstd();
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = types_table();
+        for (index, (person, types)) in self.people_toplist().into_iter().enumerate() {
+            people_table.add_row(types_row(index + 1, person.name(), &types));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = types_table();
+        for (index, (team_name, types)) in self.teams_toplist().into_iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(types_row(index + 1, name, &types));
+        }
+        teams_table.printstd();
+
+        writeln!(
+            terminal,
+            "Commits not following Conventional Commits are counted as \"other\"."
+        )?;
+
+        Ok(())
+    }
+}
+
+fn team_contribution_row(place: usize, name: &str, contribution: &ComputedContribution) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(contribution.commits),
+        contribution.contributors.to_string(),
+        contribution.first_time_contributors.to_string()
+    ]
+}
+
+impl<'a, 'b> Format for &'a ReleaseNotes<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let people = self.people_toplist();
+
+        terminal.print_header(&format!("Release {}..{}", self.from, self.to))?;
+
+        terminal.print_fact("Commits", self.commits)?;
+        terminal.print_fact("Contributors", people.len())?;
+        terminal.print_fact("First-time contributors", self.first_time_contributors.len())?;
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = new_table();
+        people_table.add_row(row![b->"#", b->"Person", b->"Commits", b->"First time"]);
+        for (index, &(person, ref contribution)) in people.iter().enumerate() {
+            let first_time = if self.is_first_time(person) { "yes" } else { "" };
+            people_table.add_row(row![
+                (index + 1).to_string(),
+                person.name(),
+                format_count(contribution.commits),
+                first_time
+            ]);
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = new_table();
+        teams_table.add_row(row![b->"#", b->"Team", b->"Commits", b->"People", b->"First time"]);
+        for (index, &(ref team_name, ref contribution)) in
+            self.teams_toplist().iter().enumerate()
+        {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(team_contribution_row(index + 1, name, contribution));
+        }
+        teams_table.printstd();
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Format for &'a NewcomerReport<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        terminal.print_header("Newcomers")?;
+
+        let since = self.since.map(dates::format_day);
+        let until = self.until.map(dates::format_day);
+        terminal.print_fact(
+            "First commits between",
+            format!(
+                "{} and {}",
+                since.as_ref().map(String::as_str).unwrap_or("the beginning"),
+                until.as_ref().map(String::as_str).unwrap_or("today")
+            ),
+        )?;
+        terminal.print_fact("Newcomers", self.newcomers.len())?;
+
+        terminal.print_headline("\nPeople")?;
+        let mut table = new_table();
+        table.add_row(row![b->"Person", b->"First commit", b->"Commits", b->"Joined", b->"Subject"]);
+        for newcomer in &self.newcomers {
+            table.add_row(row![
+                newcomer.person.name(),
+                dates::format_day(newcomer.first_commit.day),
+                newcomer.commits.to_string(),
+                newcomer.joined.map(dates::format_day).unwrap_or_else(|| String::from("-")),
+                newcomer.first_commit.subject
+            ]);
+        }
+        table.printstd();
+
+        terminal.print_headline("\nWhat they touched first")?;
+        for newcomer in &self.newcomers {
+            terminal.print_fact(newcomer.person.name(), newcomer.first_commit.files.len())?;
+            for file in newcomer.first_commit.files.iter().take(5) {
+                writeln!(terminal, "  {}", file)?;
+            }
+            if newcomer.first_commit.files.len() > 5 {
+                writeln!(terminal, "  ...and {} more", newcomer.first_commit.files.len() - 5)?;
+            }
+            if let Some(ref owned) = newcomer.first_owned {
+                writeln!(
+                    terminal,
+                    "  Oldest lines still owned: {} ({} lines from {})",
+                    owned.path,
+                    owned.lines,
+                    dates::format_day(owned.day)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn monthly_commits_row(place: usize, name: &str, commits: &MonthlyCommits) -> Row {
+    row![
+        place.to_string(),
+        name,
+        format_count(commits.total()),
+        commits.active_months().to_string(),
+        format_busiest_month(commits)
+    ]
+}
+
+fn monthly_commits_table(first_column: &str) -> Table {
+    let mut table = new_table();
+    table.add_row(row![
+        b->"#",
+        b->first_column,
+        b->"Commits",
+        b->"Active months",
+        b->"Busiest month"
+    ]);
+    table
+}
+
+impl<'a, 'b> Format for &'a Timeline<'b> {
+    fn format(&self, terminal: &mut Terminal) -> Result<()> {
+        let months = self.months();
+
+        terminal.print_header("Timeline")?;
+        terminal.print_fact("Months", months.len())?;
+        terminal.print_fact("Busiest month", format_busiest_month(&self.repository))?;
+
+        terminal.print_headline("\nCommits per month")?;
+        let max_commits = self.repository.busiest_month().map_or(0.0, |(_, commits)| commits);
+        for month in &months {
+            let commits = self.repository.get(*month);
+            let width = if max_commits > 0.0 {
+                (commits * 40.0 / max_commits).ceil() as usize
+            } else {
+                0
+            };
+            writeln!(terminal, "{} {:>8} {}", month, format_count(commits), "█".repeat(width))?;
+        }
+
+        terminal.print_headline("\nPeople")?;
+        let mut people_table = monthly_commits_table("Person");
+        for (index, (person, commits)) in self.people_toplist().into_iter().enumerate() {
+            people_table.add_row(monthly_commits_row(index + 1, person.name(), commits));
+        }
+        people_table.printstd();
+
+        terminal.print_headline("\nTeams")?;
+        let mut teams_table = monthly_commits_table("Team");
+        for (index, (team_name, commits)) in self.teams_toplist().into_iter().enumerate() {
+            let name = team_name.unwrap_or(NO_TEAM_NAME);
+            teams_table.add_row(monthly_commits_row(index + 1, name, commits));
+        }
+        teams_table.printstd();
+
         Ok(())
     }
 }
@@ -0,0 +1,168 @@
+use super::document::{Document, ToDocument};
+use errors::*;
+
+// The delimited formatters print the tables of a report as CSV or TSV to STDOUT, for loading into
+// spreadsheets. All sections go into one table: the `section` and `kind` columns tell which rows
+// belong together, and columns a section doesn't have are left empty. Cells hold plain values:
+// numbers without units or rounding, and nothing where the other formats show "-".
+pub struct Formatter {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    Comma,
+    Tab,
+}
+
+pub trait Format {
+    fn format(&self, separator: Separator) -> Result<()>;
+}
+
+impl Formatter {
+    pub fn display<F>(data: F, separator: Separator) -> Result<()>
+    where
+        F: Format,
+    {
+        data.format(separator)
+    }
+}
+
+impl<T> Format for T
+where
+    T: ToDocument,
+{
+    fn format(&self, separator: Separator) -> Result<()> {
+        print!("{}", render(&self.to_document(), separator));
+        Ok(())
+    }
+}
+
+/// Turns a column title like "Lines owned" into a header that is easy to use from code.
+fn header(column: &str) -> String {
+    if column == "#" {
+        return String::from("rank");
+    }
+    column
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Quotes a CSV field when needed (RFC 4180). TSV can't quote, so tabs and line breaks become
+/// spaces instead.
+fn field(text: &str, separator: Separator) -> String {
+    match separator {
+        Separator::Comma => {
+            if text.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text.to_owned()
+            }
+        }
+        Separator::Tab => text.replace(|c| c == '\t' || c == '\n' || c == '\r', " "),
+    }
+}
+
+fn line(fields: &[String], separator: Separator) -> String {
+    let delimiter = match separator {
+        Separator::Comma => ",",
+        Separator::Tab => "\t",
+    };
+    let fields: Vec<String> = fields.iter().map(|text| field(text, separator)).collect();
+    format!("{}\n", fields.join(delimiter))
+}
+
+fn render(document: &Document, separator: Separator) -> String {
+    // Columns are kept in the order they first appear in, so the header only changes when the
+    // report itself does.
+    let mut headers: Vec<String> = vec![String::from("section"), String::from("kind")];
+    for section in &document.sections {
+        for column in &section.columns {
+            let header = header(&column.title);
+            if !headers.contains(&header) {
+                headers.push(header);
+            }
+        }
+    }
+
+    let mut output = line(&headers, separator);
+    for section in &document.sections {
+        let positions: Vec<usize> = section
+            .columns
+            .iter()
+            .map(|column| {
+                let header = header(&column.title);
+                headers.iter().position(|h| *h == header).unwrap()
+            })
+            .collect();
+
+        for row in &section.rows {
+            let mut fields = vec![String::new(); headers.len()];
+            fields[0] = section.title.clone();
+            fields[1] = section.kind.to_owned();
+            for (position, cell) in positions.iter().zip(row) {
+                fields[*position] = cell.value.clone().unwrap_or_default();
+            }
+            output.push_str(&line(&fields, separator));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use formatters::document::Cell;
+
+    fn document() -> Document {
+        let mut document = Document::new("Ownership details");
+        {
+            let section = document.section("People", "person", vec!["#", "Person", "Lines owned"]);
+            section.data_column("Weighted");
+            section.row(vec![
+                Cell::from("1"),
+                Cell::from("Doe, \"JD\" Jane"),
+                Cell::new("12.50*", Some(String::from("12.5"))),
+                Cell::from("true"),
+            ]);
+        }
+        {
+            let section = document.section("Teams", "team", vec!["#", "Team", "Lines owned"]);
+            section.data_column("Weighted");
+            section.row(vec![
+                Cell::from("1"),
+                Cell::from("Back\tend"),
+                Cell::new("7", Some(String::from("7"))),
+                Cell::from("false"),
+            ]);
+        }
+        {
+            let section = document.section("Files", "file", vec!["File", "Share", "Owner"]);
+            section.row(vec![Cell::from("README.md"), Cell::percent(0.25), Cell::missing()]);
+        }
+        document
+    }
+
+    #[test]
+    fn it_renders_csv_with_one_header() {
+        assert_eq!(
+            render(&document(), Separator::Comma),
+            "section,kind,rank,person,lines_owned,weighted,team,file,share,owner\n\
+             People,person,1,\"Doe, \"\"JD\"\" Jane\",12.5,true,,,,\n\
+             Teams,team,1,,7,false,Back\tend,,,\n\
+             Files,file,,,,,,README.md,25,\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_tsv_without_tabs_in_fields() {
+        assert_eq!(
+            render(&document(), Separator::Tab),
+            "section\tkind\trank\tperson\tlines_owned\tweighted\tteam\tfile\tshare\towner\n\
+             People\tperson\t1\tDoe, \"JD\" Jane\t12.5\ttrue\t\t\t\t\n\
+             Teams\tteam\t1\t\t7\tfalse\tBack end\t\t\t\n\
+             Files\tfile\t\t\t\t\t\tREADME.md\t25\t\n"
+        );
+    }
+}
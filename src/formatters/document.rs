@@ -0,0 +1,1227 @@
+use ownership::{OwnershipStatistics, ComputedOwnership};
+use commits::{CommitStatistics, ComputedCommitActivity};
+use activity::{ActivityStatistics, Heatmap, WEEKDAYS};
+use streaks::{StreakStatistics, ComputedStreaks, Span};
+use churn::{ChurnStatistics, ComputedChurn};
+use hotspots::HotspotStatistics;
+use truck_factor::TruckFactorReport;
+use knowledge_loss::{KnowledgeLossReport, LossShare};
+use codeowners::CodeOwners;
+use codeowners_audit::CodeOwnersAudit;
+use line_age::{LineAgeStatistics, ComputedLineAges};
+use collaboration::CollaborationGraph;
+use coupling::CouplingStatistics;
+use messages::{MessageStatistics, ComputedMessages, Subject};
+use commit_types::{CommitTypeStatistics, ComputedTypes};
+use release_notes::{ReleaseNotes, ComputedContribution};
+use newcomers::NewcomerReport;
+use timeline::{Timeline, MonthlyCommits};
+use dates;
+use person::Person;
+use super::text::{format_age, format_busiest_month, format_count, format_lines_owned,
+                  format_net_lines, format_other_types, format_span, people_names, team_name,
+                  team_names};
+
+/// A report laid out as a title, some facts and sections of tables. Formats that render every
+/// report the same way, like Markdown, work from this instead of from the reports themselves.
+#[derive(Debug)]
+pub struct Document {
+    pub title: String,
+    pub facts: Vec<Fact>,
+    pub sections: Vec<Section>,
+    /// Remarks on how to read the report, shown after the sections.
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Fact {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug)]
+pub struct Section {
+    pub title: String,
+    /// What each row is about, like "person" or "file".
+    pub kind: &'static str,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+#[derive(Debug)]
+pub struct Column {
+    pub title: String,
+    /// Only written by data formats like CSV, for values that the other formats show as part of
+    /// another column.
+    pub data_only: bool,
+}
+
+/// A value in a table: the text people read, and the plain value data formats write instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub text: String,
+    /// Numbers without units or rounding; `None` when there is no value at all.
+    pub value: Option<String>,
+}
+
+impl Cell {
+    pub fn new<S: Into<String>>(text: S, value: Option<String>) -> Cell {
+        Cell {
+            text: text.into(),
+            value: value,
+        }
+    }
+
+    pub fn missing() -> Cell {
+        Cell::new("-", None)
+    }
+
+    pub fn optional(value: &Option<String>) -> Cell {
+        match *value {
+            Some(ref value) => Cell::from(value.clone()),
+            None => Cell::missing(),
+        }
+    }
+
+    pub fn count(count: f64) -> Cell {
+        Cell::new(format_count(count), Some(count.to_string()))
+    }
+
+    pub fn net_lines(lines: f64) -> Cell {
+        Cell::new(format_net_lines(lines), Some(lines.to_string()))
+    }
+
+    pub fn percent(fraction: f64) -> Cell {
+        Cell::new(format_percent(fraction), Some(raw_percent(fraction)))
+    }
+
+    pub fn span(span: &Option<Span>) -> Cell {
+        let days = span.as_ref().map(|span| span.days.to_string());
+        Cell::new(format_span(span), days)
+    }
+
+    pub fn age(days: i64) -> Cell {
+        Cell::new(format_age(days), Some(days.to_string()))
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Cell {
+        let value = Some(text.clone());
+        Cell::new(text, value)
+    }
+}
+
+impl<'a> From<&'a str> for Cell {
+    fn from(text: &'a str) -> Cell {
+        Cell::from(text.to_owned())
+    }
+}
+
+impl Document {
+    pub fn new<S: Into<String>>(title: S) -> Document {
+        Document {
+            title: title.into(),
+            facts: Vec::new(),
+            sections: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn fact<S: ToString>(&mut self, label: &str, value: S) {
+        self.facts.push(Fact {
+            label: label.to_owned(),
+            value: value.to_string(),
+        });
+    }
+
+    pub fn section<I, S>(&mut self, title: &str, kind: &'static str, columns: I) -> &mut Section
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sections.push(Section {
+            title: title.to_owned(),
+            kind: kind,
+            columns: columns
+                .into_iter()
+                .map(|title| {
+                    Column {
+                        title: title.into(),
+                        data_only: false,
+                    }
+                })
+                .collect(),
+            rows: Vec::new(),
+        });
+        self.sections.last_mut().unwrap()
+    }
+
+    pub fn note<S: Into<String>>(&mut self, note: S) {
+        self.notes.push(note.into());
+    }
+}
+
+impl Section {
+    /// Adds a column that only data formats write, after the columns added so far.
+    pub fn data_column(&mut self, title: &str) -> &mut Section {
+        self.columns.push(Column {
+            title: title.to_owned(),
+            data_only: true,
+        });
+        self
+    }
+
+    pub fn row<C: Into<Cell>>(&mut self, cells: Vec<C>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// The columns and cells of a row that people are meant to read.
+    pub fn shown_columns(&self) -> Vec<&Column> {
+        self.columns.iter().filter(|column| !column.data_only).collect()
+    }
+
+    pub fn shown_cells<'a>(&self, row: &'a [Cell]) -> Vec<&'a Cell> {
+        self.columns
+            .iter()
+            .zip(row)
+            .filter(|&(column, _)| !column.data_only)
+            .map(|(_, cell)| cell)
+            .collect()
+    }
+}
+
+pub trait ToDocument {
+    fn to_document(&self) -> Document;
+}
+
+impl<'a, T> ToDocument for &'a T
+where
+    T: ToDocument,
+{
+    fn to_document(&self) -> Document {
+        (*self).to_document()
+    }
+}
+
+fn format_percent(fraction: f64) -> String {
+    format!("{:.2}%", fraction * 100.0)
+}
+
+/// Percentages for data formats. Some fractions are only kept as `f32`, so they are rounded to
+/// hide the noise of converting them.
+fn raw_percent(fraction: f64) -> String {
+    ((fraction * 100.0 * 10_000.0).round() / 10_000.0).to_string()
+}
+
+fn rounded_percent_cell(fraction: f64) -> Cell {
+    Cell::new(format!("{:.0}%", fraction * 100.0), Some(raw_percent(fraction)))
+}
+
+fn lines_owned_cell(score: &ComputedOwnership) -> Cell {
+    Cell::new(format_lines_owned(score), Some(score.total_lines_owned.to_string()))
+}
+
+impl<'b> ToDocument for OwnershipStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Ownership details");
+        document.fact("Total lines", self.total_lines());
+
+        let people = self.people_toplist();
+        let teams = self.teams_toplist();
+
+        {
+            let section = document.section(
+                "People",
+                "person",
+                vec!["#", "Person", "Lines owned", "Percent of total"],
+            );
+            section.data_column("Weighted");
+            for (index, &(person, ref score)) in people.iter().enumerate() {
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(person.name().to_owned()),
+                    lines_owned_cell(score),
+                    Cell::percent(f64::from(score.fraction_owned)),
+                    Cell::from(score.weighted.to_string()),
+                ]);
+            }
+        }
+        {
+            let section = document.section(
+                "Teams",
+                "team",
+                vec!["#", "Team", "Lines owned", "Percent of total"],
+            );
+            section.data_column("Weighted");
+            for (index, &(name, ref score)) in teams.iter().enumerate() {
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(team_name(name)),
+                    lines_owned_cell(score),
+                    Cell::percent(f64::from(score.fraction_owned)),
+                    Cell::from(score.weighted.to_string()),
+                ]);
+            }
+        }
+
+        for note in self.notes() {
+            document.note(note);
+        }
+        document
+    }
+}
+
+fn commit_activity_row(
+    place: usize,
+    name: String,
+    activity: &ComputedCommitActivity,
+) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(activity.commits),
+        Cell::optional(&activity.first_commit),
+        Cell::optional(&activity.last_commit),
+        Cell::from(activity.active_days.to_string()),
+        Cell::new(
+            format!("{:.2}", activity.commits_per_active_day),
+            Some(activity.commits_per_active_day.to_string()),
+        ),
+    ]
+}
+
+fn commit_activity_columns(first_column: &str) -> Vec<&str> {
+    vec![
+        "#",
+        first_column,
+        "Commits",
+        "First commit",
+        "Last commit",
+        "Active days",
+        "Commits per day",
+    ]
+}
+
+impl<'b> ToDocument for CommitStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Commit activity");
+
+        let repository = self.repository_activity();
+        document.fact("Total commits", format_count(repository.commits));
+        if let Some(ref first_commit) = repository.first_commit {
+            document.fact("First commit", first_commit);
+        }
+        if let Some(ref last_commit) = repository.last_commit {
+            document.fact("Last commit", last_commit);
+        }
+        document.fact("Active days", repository.active_days);
+        document.fact(
+            "Commits per active day",
+            format!("{:.2}", repository.commits_per_active_day),
+        );
+
+        {
+            let section = document.section("People", "person", commit_activity_columns("Person"));
+            for (index, (person, activity)) in self.people_toplist().into_iter().enumerate() {
+                section.row(commit_activity_row(index + 1, person.name().to_owned(), &activity));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", commit_activity_columns("Team"));
+            for (index, (name, activity)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(commit_activity_row(index + 1, team_name(name), &activity));
+            }
+        }
+        document
+    }
+}
+
+fn hour_cell(hour: Option<usize>) -> Cell {
+    match hour {
+        Some(hour) => Cell::new(format!("{:02}:00-{:02}:59", hour, hour), Some(hour.to_string())),
+        None => Cell::missing(),
+    }
+}
+
+fn heatmap_summary_row(place: usize, name: String, heatmap: &Heatmap) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(heatmap.total()),
+        Cell::optional(&heatmap.busiest_weekday().map(|weekday| WEEKDAYS[weekday].to_owned())),
+        hour_cell(heatmap.busiest_hour()),
+        Cell::count(heatmap.at_night()),
+    ]
+}
+
+fn heatmap_summary_columns(first_column: &str) -> Vec<&str> {
+    vec!["#", first_column, "Commits", "Busiest day", "Busiest hour", "Night commits"]
+}
+
+impl<'b> ToDocument for ActivityStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Activity");
+
+        document.fact("Total commits", format_count(self.repository.total()));
+        if let Some(weekday) = self.repository.busiest_weekday() {
+            document.fact("Busiest day", WEEKDAYS[weekday]);
+        }
+        document.fact("Busiest hour", hour_cell(self.repository.busiest_hour()).text);
+        if let Some((person, commits)) = self.night_owl() {
+            document.fact(
+                "Night owl",
+                format!(
+                    "{} ({} commits between 00:00 and 06:00)",
+                    person.name(),
+                    format_count(commits)
+                ),
+            );
+        }
+        if let Some((name, commits)) = self.friday_team() {
+            document.fact(
+                "Ships on Fridays",
+                format!("{} ({} commits)", team_name(name), format_count(commits)),
+            );
+        }
+
+        {
+            let mut columns = vec![String::from("Day")];
+            columns.extend((0..24).map(|hour| format!("{:02}", hour)));
+            columns.push(String::from("Total"));
+
+            let section = document.section("Repository", "weekday", columns);
+            for (weekday, weekday_name) in WEEKDAYS.iter().enumerate() {
+                let mut row = vec![Cell::from(&weekday_name[0..3])];
+                row.extend((0..24).map(|hour| Cell::count(self.repository.get(weekday, hour))));
+                row.push(Cell::count(self.repository.on_weekday(weekday)));
+                section.row(row);
+            }
+        }
+        {
+            let section = document.section("People", "person", heatmap_summary_columns("Person"));
+            for (index, (person, heatmap)) in self.people_toplist().into_iter().enumerate() {
+                section.row(heatmap_summary_row(index + 1, person.name().to_owned(), heatmap));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", heatmap_summary_columns("Team"));
+            let teams = self.teams_toplist()
+                .into_iter()
+                .filter(|&(_, heatmap)| heatmap.total() > 0.0);
+            for (index, (name, heatmap)) in teams.enumerate() {
+                section.row(heatmap_summary_row(index + 1, team_name(name), heatmap));
+            }
+        }
+        document
+    }
+}
+
+fn streaks_row(place: usize, name: String, streaks: &ComputedStreaks) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::span(&streaks.longest_streak),
+        Cell::from(streaks.current_streak.to_string()),
+        Cell::span(&streaks.longest_gap),
+    ]
+}
+
+impl<'b> ToDocument for StreakStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Streaks");
+
+        if let Some((person, streak)) = self.longest_streak_by_anyone() {
+            document.fact(
+                "Longest streak by anyone",
+                format!("{} by {}", format_span(&Some(streak)), person.name()),
+            );
+        }
+        if let Some((person, days)) = self.longest_current_streak() {
+            document.fact("Longest current streak", format!("{} days by {}", days, person.name()));
+        }
+        let repository = self.repository_streaks();
+        document.fact("Longest streak of the repository", format_span(&repository.longest_streak));
+        document.fact(
+            "Longest time the repository went untouched",
+            format_span(&repository.longest_gap),
+        );
+
+        let columns = |first_column| {
+            vec!["#", first_column, "Longest streak", "Current streak", "Longest gap"]
+        };
+        {
+            let section = document.section("People", "person", columns("Person"));
+            for (index, (person, streaks)) in self.people_toplist().into_iter().enumerate() {
+                section.row(streaks_row(index + 1, person.name().to_owned(), &streaks));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", columns("Team"));
+            for (index, (name, streaks)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(streaks_row(index + 1, team_name(name), &streaks));
+            }
+        }
+        document
+    }
+}
+
+fn churn_row(place: usize, name: String, churn: &ComputedChurn) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(churn.commits),
+        Cell::count(churn.insertions),
+        Cell::count(churn.deletions),
+        Cell::net_lines(churn.net_lines),
+        Cell::from(churn.files_touched.to_string()),
+    ]
+}
+
+fn churn_columns(first_column: &str) -> Vec<&str> {
+    vec!["#", first_column, "Commits", "Added", "Removed", "Net lines", "Files touched"]
+}
+
+impl<'b> ToDocument for ChurnStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Churn");
+
+        let repository = self.repository_churn();
+        document.fact("Commits", format_count(repository.commits));
+        document.fact("Lines added", format_count(repository.insertions));
+        document.fact("Lines removed", format_count(repository.deletions));
+        document.fact("Net lines", format_net_lines(repository.net_lines));
+        document.fact("Files touched", repository.files_touched);
+
+        {
+            let section = document.section("People", "person", churn_columns("Person"));
+            for (index, (person, churn)) in self.people_toplist().into_iter().enumerate() {
+                section.row(churn_row(index + 1, person.name().to_owned(), &churn));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", churn_columns("Team"));
+            for (index, (name, churn)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(churn_row(index + 1, team_name(name), &churn));
+            }
+        }
+        {
+            let section = document.section(
+                "Net lines leaderboard",
+                "person",
+                vec!["#", "Person", "Net lines"],
+            );
+            for (index, (person, churn)) in self.net_lines_leaderboard().into_iter().enumerate() {
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(person.name()),
+                    Cell::net_lines(churn.net_lines),
+                ]);
+            }
+        }
+        {
+            let section = document.section(
+                "Biggest commits",
+                "commit",
+                vec!["#", "Commit", "Person", "Added", "Removed", "Summary"],
+            );
+            for (index, commit) in self.biggest_commits.iter().enumerate() {
+                section.row(vec![
+                    (index + 1).to_string(),
+                    commit.sha[0..8].to_owned(),
+                    commit.person.name().to_owned(),
+                    commit.insertions.to_string(),
+                    commit.deletions.to_string(),
+                    commit.summary.clone(),
+                ]);
+            }
+        }
+        document
+    }
+}
+
+impl ToDocument for HotspotStatistics {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Hotspots");
+        {
+            let section = document.section(
+                "Files",
+                "file",
+                vec!["#", "File", "Commits", "Authors", "Lines", "Score"],
+            );
+            for (index, hotspot) in self.files.iter().enumerate() {
+                section.row(vec![
+                    (index + 1).to_string(),
+                    hotspot.path.clone(),
+                    hotspot.commits.to_string(),
+                    hotspot.authors.to_string(),
+                    hotspot.lines.to_string(),
+                    hotspot.score.to_string(),
+                ]);
+            }
+        }
+        document
+    }
+}
+
+impl<'b> ToDocument for TruckFactorReport<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Truck factor");
+
+        document.fact("Files", self.people.files);
+        document.fact("Truck factor", self.people.factor());
+        document.fact("Critical people", people_names(&self.people.critical));
+        document.fact("Team truck factor", self.teams.factor());
+        document.fact("Critical teams", team_names(&self.teams.critical));
+
+        {
+            let section = document.section(
+                "Directories",
+                "directory",
+                vec![
+                    "Directory",
+                    "Files",
+                    "Truck factor",
+                    "Critical people",
+                    "Team truck factor",
+                    "Critical teams",
+                ],
+            );
+            for directory in &self.directories {
+                section.row(vec![
+                    directory.path.clone(),
+                    directory.people.files.to_string(),
+                    directory.people.factor().to_string(),
+                    people_names(&directory.people.critical),
+                    directory.teams.factor().to_string(),
+                    team_names(&directory.teams.critical),
+                ]);
+            }
+        }
+
+        document.note(format!(
+            "People owning at least {:.0}% of a file, or most of it, are counted as its owners.",
+            self.owner_threshold * 100.0
+        ));
+        document
+    }
+}
+
+fn loss_row(name: String, loss: &LossShare) -> Vec<Cell> {
+    vec![
+        Cell::from(name),
+        Cell::new(format!("{:.0}", loss.total_lines), Some(loss.total_lines.to_string())),
+        Cell::new(format!("{:.0}", loss.departed_lines), Some(loss.departed_lines.to_string())),
+        Cell::percent(loss.fraction_departed()),
+    ]
+}
+
+impl<'b> ToDocument for KnowledgeLossReport<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Knowledge loss");
+
+        document.fact("Departed people owning code", self.departed.len());
+        document.fact(
+            "Lines owned by departed people",
+            format!(
+                "{:.0} of {:.0} ({})",
+                self.repository.departed_lines,
+                self.repository.total_lines,
+                format_percent(self.repository.fraction_departed())
+            ),
+        );
+        document.fact("Orphaned files", self.orphaned_files.len());
+
+        {
+            let section = document.section(
+                "Departed people",
+                "person",
+                vec!["Person", "Left", "Lines still owned"],
+            );
+            for departed in &self.departed {
+                section.row(vec![
+                    Cell::from(departed.person.name()),
+                    Cell::optional(&departed.person.left().map(String::from)),
+                    Cell::from(departed.lines.to_string()),
+                ]);
+            }
+        }
+        {
+            let section = document.section(
+                "Directories",
+                "directory",
+                vec!["Directory", "Lines", "Departed lines", "Departed share"],
+            );
+            for directory in &self.directories {
+                section.row(loss_row(directory.path.clone(), &directory.loss));
+            }
+        }
+        {
+            let section = document.section(
+                "Teams",
+                "team",
+                vec!["Team", "Lines", "Departed lines", "Departed share"],
+            );
+            for &(name, ref loss) in &self.teams {
+                section.row(loss_row(team_name(name), loss));
+            }
+        }
+        {
+            let section = document.section(
+                "Orphaned files",
+                "file",
+                vec!["File", "Lines", "Departed lines", "Former owners"],
+            );
+            for file in &self.orphaned_files {
+                section.row(vec![
+                    file.path.clone(),
+                    file.lines.to_string(),
+                    file.departed_lines.to_string(),
+                    people_names(&file.former_owners),
+                ]);
+            }
+        }
+
+        document.note(format!(
+            "Files where more than {:.0}% of the lines are owned by departed people are orphaned.",
+            self.threshold * 100.0
+        ));
+        document
+    }
+}
+
+impl ToDocument for CodeOwners {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("CODEOWNERS");
+        {
+            let section = document.section("Rules", "rule", vec!["Pattern", "Owners"]);
+            for rule in &self.rules {
+                section.row(vec![rule.pattern.clone(), rule.owners.join(" ")]);
+            }
+        }
+        document.note("Later rules take precedence.");
+        document
+    }
+}
+
+impl<'b> ToDocument for CodeOwnersAudit<'b> {
+    fn to_document(&self) -> Document {
+        let weak_rules = self.weak_rules();
+        let unused_rules = self.unused_rules();
+
+        let mut document = Document::new("CODEOWNERS audit");
+        document.fact("File", &self.path);
+        document.fact("Rules", self.rules.len());
+        document.fact("Rules with owners that own almost nothing", weak_rules.len());
+        document.fact("Rules matching no files", unused_rules.len());
+        document.fact("Files without a rule", self.unowned_files.len());
+        document.fact("Big owners listed nowhere", self.unlisted_owners.len());
+
+        {
+            let section = document.section(
+                "Rules with owners that own almost nothing",
+                "rule",
+                vec![
+                    "Line",
+                    "Pattern",
+                    "Listed owners",
+                    "Files",
+                    "Owned by listed",
+                    "Actual owners",
+                ],
+            );
+            for rule in weak_rules {
+                let mut listed = rule.rule.owners.join(", ");
+                if !rule.unknown_owners.is_empty() {
+                    listed.push_str(&format!(" (unknown: {})", rule.unknown_owners.join(", ")));
+                }
+                let actual: Vec<&Person> = rule.actual_owners
+                    .iter()
+                    .map(|&(person, _)| person)
+                    .collect();
+                section.row(vec![
+                    Cell::from(rule.line.to_string()),
+                    Cell::from(rule.rule.pattern.clone()),
+                    Cell::from(listed),
+                    Cell::from(rule.files.to_string()),
+                    Cell::percent(rule.fraction_owned_by_listed()),
+                    Cell::from(people_names(&actual)),
+                ]);
+            }
+        }
+        {
+            let section = document.section(
+                "Rules matching no files",
+                "rule",
+                vec!["Line", "Pattern", "Listed owners"],
+            );
+            for rule in unused_rules {
+                section.row(vec![
+                    rule.line.to_string(),
+                    rule.rule.pattern.clone(),
+                    rule.rule.owners.join(", "),
+                ]);
+            }
+        }
+        {
+            let section = document.section("Files without a rule", "file", vec!["File"]);
+            for path in &self.unowned_files {
+                section.row(vec![path.clone()]);
+            }
+        }
+        {
+            let section = document.section(
+                "Big owners listed nowhere",
+                "person",
+                vec!["Person", "Lines owned", "Share of repository"],
+            );
+            for owner in &self.unlisted_owners {
+                section.row(vec![
+                    Cell::from(owner.person.name()),
+                    Cell::from(owner.lines.to_string()),
+                    Cell::percent(owner.fraction_owned),
+                ]);
+            }
+        }
+
+        document.note(format!(
+            "Rules are weak when their listed owners own less than {:.0}% of the lines they match.",
+            self.threshold * 100.0
+        ));
+        document
+    }
+}
+
+fn line_age_row(name: String, ages: &ComputedLineAges) -> Vec<Cell> {
+    let mut row = vec![Cell::from(name), Cell::count(ages.lines)];
+    for percent in &[10, 25, 50, 75, 90] {
+        row.push(
+            ages.percentiles
+                .iter()
+                .find(|percentile| percentile.percent == *percent)
+                .map(|percentile| Cell::age(percentile.age_days))
+                .unwrap_or_else(Cell::missing),
+        );
+    }
+    row
+}
+
+fn line_age_columns(first_column: &str) -> Vec<&str> {
+    vec![first_column, "Lines", "10%", "25%", "Median", "75%", "90%"]
+}
+
+impl<'b> ToDocument for LineAgeStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let repository = self.repository_ages();
+
+        let mut document = Document::new("Line age");
+        document.fact("Surviving lines", format_count(repository.lines));
+        document.fact(
+            "Median age",
+            repository.median_age_days.map(format_age).unwrap_or_else(|| String::from("-")),
+        );
+        if let Some(ref oldest) = self.oldest_line {
+            document.fact(
+                "Oldest surviving line",
+                format!(
+                    "{}:{}, last changed {} by {} in {:.7}: {}",
+                    oldest.path,
+                    oldest.line_number,
+                    dates::format_day(oldest.day),
+                    oldest.person.name(),
+                    oldest.commit.to_string(),
+                    oldest.content.trim()
+                ),
+            );
+        }
+
+        {
+            let section = document.section("Lines per year", "year", vec!["Year", "Lines"]);
+            for (year, lines) in &repository.lines_per_year {
+                section.row(vec![year.to_string(), lines.to_string()]);
+            }
+        }
+        {
+            let section = document.section(
+                "Directories",
+                "directory",
+                line_age_columns("Directory"),
+            );
+            for (path, ages) in self.directories() {
+                section.row(line_age_row(path.to_owned(), &ages));
+            }
+        }
+        {
+            let section = document.section("People", "person", line_age_columns("Name"));
+            for (person, ages) in self.people_toplist() {
+                section.row(line_age_row(person.name().to_owned(), &ages));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", line_age_columns("Team"));
+            for (name, ages) in self.teams_toplist() {
+                section.row(line_age_row(team_name(name), &ages));
+            }
+        }
+
+        document.note("Ages are counted from the last change to each line.");
+        document
+    }
+}
+
+impl ToDocument for CollaborationGraph {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Collaboration");
+        document.fact(if self.teams { "Teams" } else { "People" }, self.nodes.len());
+        document.fact("Connections", self.edges.len());
+        document.fact("Time window", format!("{} days", self.window_days));
+
+        {
+            let section = document.section(
+                "Connections",
+                "connection",
+                vec!["#", "Between", "And", "Shared files", "Co-authored commits"],
+            );
+            for (index, edge) in self.edges.iter().enumerate() {
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(edge.from.clone()),
+                    Cell::from(edge.to.clone()),
+                    Cell::count(edge.shared_files),
+                    Cell::count(edge.co_authored_commits),
+                ]);
+            }
+        }
+        document
+    }
+}
+
+impl ToDocument for CouplingStatistics {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Temporal coupling");
+        document.fact("Commits analyzed", self.commits_analyzed);
+        document.fact("Big commits skipped", self.commits_skipped);
+
+        {
+            let section = document.section(
+                "Files changing together",
+                "pair",
+                vec!["#", "File", "Coupled file", "Shared commits", "Confidence", "Degree"],
+            );
+            for (index, pair) in self.pairs.iter().enumerate() {
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(pair.file.clone()),
+                    Cell::from(pair.coupled_file.clone()),
+                    Cell::new(
+                        format!(
+                            "{} of {}/{}",
+                            pair.support,
+                            pair.revisions,
+                            pair.coupled_revisions
+                        ),
+                        Some(pair.support.to_string()),
+                    ),
+                    rounded_percent_cell(pair.confidence),
+                    rounded_percent_cell(pair.degree),
+                ]);
+            }
+        }
+        document
+    }
+}
+
+fn add_subjects(document: &mut Document, title: &str, subjects: &[Subject]) {
+    let section = document.section(title, "commit", vec!["Length", "Subject", "Author", "Commit"]);
+    for subject in subjects {
+        section.row(vec![
+            subject.length().to_string(),
+            subject.subject.clone(),
+            subject.person.name().to_owned(),
+            subject.sha[0..7].to_owned(),
+        ]);
+    }
+}
+
+fn messages_row(place: usize, name: String, messages: &ComputedMessages) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(messages.commits),
+        rounded_percent_cell(messages.conventional_share),
+        Cell::new(
+            format!("{:.1}", messages.average_subject_length),
+            Some(messages.average_subject_length.to_string()),
+        ),
+        Cell::count(messages.emoji),
+        Cell::optional(&messages.favorite_emoji),
+        Cell::optional(&messages.most_used_word),
+    ]
+}
+
+fn messages_columns(first_column: &str) -> Vec<&str> {
+    vec![
+        "#",
+        first_column,
+        "Commits",
+        "Conventional",
+        "Avg. subject",
+        "Emoji",
+        "Favorite emoji",
+        "Most used word",
+    ]
+}
+
+impl<'b> ToDocument for MessageStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let repository = self.repository_messages();
+
+        let mut document = Document::new("Commit messages");
+        document.fact("Commits", format_count(repository.commits));
+        document.fact(
+            "Following Conventional Commits",
+            format!(
+                "{} ({})",
+                format_count(repository.conventional_commits),
+                format_percent(repository.conventional_share)
+            ),
+        );
+        document.fact(
+            "Average subject length",
+            format!("{:.1} characters", repository.average_subject_length),
+        );
+        document.fact("Emoji used", format_count(repository.emoji));
+        if let Some(ref emoji) = repository.favorite_emoji {
+            document.fact("Favorite emoji", emoji);
+        }
+
+        add_subjects(&mut document, "Longest subjects", &self.longest_subjects);
+        add_subjects(&mut document, "Shortest subjects", &self.shortest_subjects);
+        {
+            let section = document.section(
+                "Most common first words",
+                "word",
+                vec!["Word", "Subjects"],
+            );
+            for word in &repository.first_words {
+                section.row(vec![Cell::from(word.word.clone()), Cell::count(word.count)]);
+            }
+        }
+        {
+            let section = document.section("People", "person", messages_columns("Person"));
+            for (index, (person, messages)) in self.people_toplist().into_iter().enumerate() {
+                section.row(messages_row(index + 1, person.name().to_owned(), &messages));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", messages_columns("Team"));
+            for (index, (name, messages)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(messages_row(index + 1, team_name(name), &messages));
+            }
+        }
+        document
+    }
+}
+
+fn types_row(place: usize, name: String, types: &ComputedTypes) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(types.commits),
+        Cell::count(types.count("feat")),
+        Cell::count(types.count("fix")),
+        Cell::from(format_other_types(types)),
+        Cell::count(types.breaking_changes),
+    ]
+}
+
+fn types_columns(first_column: &str) -> Vec<&str> {
+    vec!["#", first_column, "Commits", "Features", "Fixes", "Other types", "Breaking"]
+}
+
+impl<'b> ToDocument for CommitTypeStatistics<'b> {
+    fn to_document(&self) -> Document {
+        let repository = self.repository_types();
+
+        let mut document = Document::new("Commit types");
+        document.fact("Commits", format_count(repository.commits));
+        document.fact("Features", format_count(repository.count("feat")));
+        document.fact("Fixes", format_count(repository.count("fix")));
+        document.fact("Other types", format_other_types(&repository));
+        document.fact("Breaking changes", format_count(repository.breaking_changes));
+
+        {
+            let section = document.section(
+                "Breaking changes by scope",
+                "scope",
+                vec!["Scope", "Breaking changes"],
+            );
+            for (scope, count) in &self.breaking_changes_by_scope {
+                section.row(vec![scope.clone(), count.to_string()]);
+            }
+        }
+        {
+            let section = document.section("People", "person", types_columns("Person"));
+            for (index, (person, types)) in self.people_toplist().into_iter().enumerate() {
+                section.row(types_row(index + 1, person.name().to_owned(), &types));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", types_columns("Team"));
+            for (index, (name, types)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(types_row(index + 1, team_name(name), &types));
+            }
+        }
+
+        document.note("Commits not following Conventional Commits are counted as \"other\".");
+        document
+    }
+}
+
+fn team_contribution_row(
+    place: usize,
+    name: String,
+    contribution: &ComputedContribution,
+) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(contribution.commits),
+        Cell::from(contribution.contributors.to_string()),
+        Cell::from(contribution.first_time_contributors.to_string()),
+    ]
+}
+
+impl<'b> ToDocument for ReleaseNotes<'b> {
+    fn to_document(&self) -> Document {
+        let people = self.people_toplist();
+
+        let mut document = Document::new(format!("Contributors to {}..{}", self.from, self.to));
+        document.fact("Commits", self.commits);
+        document.fact("Contributors", people.len());
+        document.fact("First-time contributors", self.first_time_contributors.len());
+
+        {
+            let section = document.section(
+                "People",
+                "person",
+                vec!["#", "Person", "Commits", "First time"],
+            );
+            for (index, &(person, ref contribution)) in people.iter().enumerate() {
+                let first_time = self.is_first_time(person);
+                section.row(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(person.name()),
+                    Cell::count(contribution.commits),
+                    Cell::new(if first_time { "🎉" } else { "" }, Some(first_time.to_string())),
+                ]);
+            }
+        }
+        {
+            let section = document.section(
+                "Teams",
+                "team",
+                vec!["#", "Team", "Commits", "People", "First time"],
+            );
+            for (index, (name, contribution)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(team_contribution_row(index + 1, team_name(name), &contribution));
+            }
+        }
+        document
+    }
+}
+
+impl<'b> ToDocument for NewcomerReport<'b> {
+    fn to_document(&self) -> Document {
+        let mut document = Document::new("Newcomers");
+        document.fact(
+            "First commits between",
+            format!(
+                "{} and {}",
+                self.since.map(dates::format_day).unwrap_or_else(|| String::from("the beginning")),
+                self.until.map(dates::format_day).unwrap_or_else(|| String::from("today"))
+            ),
+        );
+        document.fact("Newcomers", self.newcomers.len());
+
+        {
+            let section = document.section(
+                "People",
+                "person",
+                vec![
+                    "Person",
+                    "First commit",
+                    "Commits",
+                    "Joined",
+                    "Subject",
+                    "Files touched first",
+                    "Oldest lines owned",
+                ],
+            );
+            for newcomer in &self.newcomers {
+                section.row(vec![
+                    Cell::from(newcomer.person.name()),
+                    Cell::from(dates::format_day(newcomer.first_commit.day)),
+                    Cell::from(newcomer.commits.to_string()),
+                    Cell::optional(&newcomer.joined.map(dates::format_day)),
+                    Cell::from(newcomer.first_commit.subject.clone()),
+                    Cell::from(newcomer.first_commit.files.join(", ")),
+                    Cell::optional(&newcomer.first_owned.as_ref().map(|owned| {
+                        format!("{} ({})", owned.path, dates::format_day(owned.day))
+                    })),
+                ]);
+            }
+        }
+        document
+    }
+}
+
+fn monthly_commits_row(place: usize, name: String, commits: &MonthlyCommits) -> Vec<Cell> {
+    vec![
+        Cell::from(place.to_string()),
+        Cell::from(name),
+        Cell::count(commits.total()),
+        Cell::from(commits.active_months().to_string()),
+        Cell::new(
+            format_busiest_month(commits),
+            commits.busiest_month().map(|(month, _)| month.to_string()),
+        ),
+    ]
+}
+
+fn monthly_commits_columns(first_column: &str) -> Vec<&str> {
+    vec!["#", first_column, "Commits", "Active months", "Busiest month"]
+}
+
+impl<'b> ToDocument for Timeline<'b> {
+    fn to_document(&self) -> Document {
+        let months = self.months();
+
+        let mut document = Document::new("Timeline");
+        document.fact("Months", months.len());
+        document.fact("Busiest month", format_busiest_month(&self.repository));
+
+        {
+            let section = document.section("Commits per month", "month", vec!["Month", "Commits"]);
+            for month in &months {
+                section.row(vec![
+                    Cell::from(month.to_string()),
+                    Cell::count(self.repository.get(*month)),
+                ]);
+            }
+        }
+        {
+            let section = document.section("People", "person", monthly_commits_columns("Person"));
+            for (index, (person, commits)) in self.people_toplist().into_iter().enumerate() {
+                section.row(monthly_commits_row(index + 1, person.name().to_owned(), commits));
+            }
+        }
+        {
+            let section = document.section("Teams", "team", monthly_commits_columns("Team"));
+            for (index, (name, commits)) in self.teams_toplist().into_iter().enumerate() {
+                section.row(monthly_commits_row(index + 1, team_name(name), commits));
+            }
+        }
+        document
+    }
+}
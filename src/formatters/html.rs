@@ -0,0 +1,236 @@
+extern crate serde_json;
+
+use std::io::Write;
+
+use super::charts::{self, escape};
+use super::document::{Document, ToDocument};
+use report::Report;
+use errors::*;
+
+// The HTML formatter prints a complete, static page to STDOUT. Styles and scripts are inlined so
+// the page works as a CI artifact without any network access.
+pub struct Formatter {}
+
+pub trait Format {
+    fn format(&self) -> Result<()>;
+}
+
+impl Formatter {
+    pub fn display<F>(data: F) -> Result<()>
+    where
+        F: Format,
+    {
+        data.format()
+    }
+}
+
+impl<T> Format for T
+where
+    T: ToDocument,
+{
+    fn format(&self) -> Result<()> {
+        let document = self.to_document();
+        let mut body = format!("<h1>{}</h1>\n", escape(&document.title));
+        body.push_str(&render_document(&document, 2, |_| true));
+        print!("{}", page(&document.title, &body));
+        Ok(())
+    }
+}
+
+const STYLE: &'static str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #24292e;
+       max-width: 1000px; margin: 2em auto; padding: 0 1em; line-height: 1.5; }
+h1, h2 { border-bottom: 1px solid #eaecef; padding-bottom: .3em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #dfe2e5; padding: 4px 10px; text-align: left; }
+tr:nth-child(2n) td { background: #f6f8fa; }
+table.sortable th { cursor: pointer; user-select: none; background: #f6f8fa; }
+th[aria-sort=ascending]::after { content: " \25B2"; }
+th[aria-sort=descending]::after { content: " \25BC"; }
+.facts { list-style: none; padding: 0; }
+.note, .empty, .generated { color: #6a737d; }
+svg { display: block; max-width: 100%; height: auto; margin: 1em 0; }
+"#;
+
+const SCRIPT: &'static str = r#"
+(function () {
+  function value(cell) {
+    var text = cell.textContent.trim();
+    if (/^[+-]?\d+(\.\d+)?[%*]?$/.test(text)) {
+      return parseFloat(text.replace(/[%*+]/g, ''));
+    }
+    return text.toLowerCase();
+  }
+
+  function compare(a, b) {
+    if (typeof a !== typeof b) {
+      return typeof a === 'number' ? -1 : 1;
+    }
+    return a < b ? -1 : a > b ? 1 : 0;
+  }
+
+  Array.prototype.forEach.call(document.querySelectorAll('table.sortable'), function (table) {
+    var headers = table.tHead.rows[0].cells;
+    Array.prototype.forEach.call(headers, function (header, column) {
+      header.addEventListener('click', function () {
+        var ascending = header.getAttribute('aria-sort') !== 'ascending';
+        Array.prototype.forEach.call(headers, function (other) {
+          other.removeAttribute('aria-sort');
+        });
+        header.setAttribute('aria-sort', ascending ? 'ascending' : 'descending');
+
+        var body = table.tBodies[0];
+        var rows = Array.prototype.slice.call(body.rows);
+        rows.sort(function (a, b) {
+          var order = compare(value(a.cells[column]), value(b.cells[column]));
+          return ascending ? order : -order;
+        });
+        rows.forEach(function (row) { body.appendChild(row); });
+      });
+    });
+  });
+})();
+"#;
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}<script>{}</script>\n\
+         </body>\n</html>\n",
+        escape(title),
+        STYLE,
+        body,
+        SCRIPT
+    )
+}
+
+/// Renders the facts, the sections picked by `include` and the notes of a document, with section
+/// titles at the given heading level.
+fn render_document<F>(document: &Document, level: usize, include: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    let mut html = String::new();
+
+    if !document.facts.is_empty() {
+        html.push_str("<ul class=\"facts\">\n");
+        for fact in &document.facts {
+            html.push_str(&format!(
+                "<li><strong>{}:</strong> {}</li>\n",
+                escape(&fact.label),
+                escape(&fact.value)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    for section in document.sections.iter().filter(|section| include(section.kind)) {
+        html.push_str(&format!("<h{}>{}</h{}>\n", level, escape(&section.title), level));
+        if section.rows.is_empty() {
+            html.push_str("<p class=\"empty\">None</p>\n");
+            continue;
+        }
+
+        html.push_str("<table class=\"sortable\">\n<thead><tr>");
+        for column in section.shown_columns() {
+            html.push_str(&format!("<th>{}</th>", escape(&column.title)));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for row in &section.rows {
+            html.push_str("<tr>");
+            for cell in section.shown_cells(row) {
+                html.push_str(&format!("<td>{}</td>", escape(&cell.text)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    for note in &document.notes {
+        html.push_str(&format!("<p class=\"note\">{}</p>\n", escape(note)));
+    }
+
+    html
+}
+
+/// Makes JSON safe to put into a script element, which closing tags inside the data would end
+/// early. JSON reads `<\/` the same as `</`.
+fn script_data(json: &str) -> String {
+    json.replace("</", "<\\/")
+}
+
+/// Writes the whole report as one page, with the serialized report data embedded for anyone who
+/// wants to build on it.
+pub fn write_report<W>(report: &Report, out: &mut W) -> Result<()>
+where
+    W: Write,
+{
+    let mut body = String::from("<h1>Git trivia</h1>\n");
+    body.push_str(&format!(
+        "<p class=\"generated\">Generated on {} at <code>{}</code>.</p>\n",
+        escape(&report.generated_on),
+        escape(&report.head_sha)
+    ));
+
+    body.push_str("<h2>Ownership</h2>\n");
+    body.push_str(&render_document(&report.ownership.to_document(), 3, |_| true));
+
+    body.push_str("<h2>Commits</h2>\n");
+    body.push_str(&render_document(&report.commits.to_document(), 3, |_| true));
+
+    body.push_str("<h2>Activity</h2>\n");
+    body.push_str(&charts::heatmap(&report.activity.repository).to_svg());
+    // The heatmap chart above replaces the table of commits per weekday.
+    body.push_str(&render_document(
+        &report.activity.to_document(),
+        3,
+        |kind| kind != "weekday",
+    ));
+
+    body.push_str("<h2>Timeline</h2>\n<p>Commits per month.</p>\n");
+    body.push_str(&charts::timeline(&report.timeline).to_svg());
+
+    body.push_str(&format!(
+        "\n<script type=\"application/json\" id=\"trivia-data\">{}</script>\n",
+        script_data(&serde_json::to_string(report)?)
+    ));
+
+    out.write_all(page("Git trivia", &body).as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_escapes_documents() {
+        let mut document = Document::new("Ownership");
+        document.fact("Top <file>", "a & b");
+        {
+            let section = document.section("People", "person", vec!["Person", "Lines owned"]);
+            section.data_column("Weighted");
+            section.row(vec!["<script>alert(\"hi\")</script>", "3.00*", "true"]);
+        }
+        document.note("* Lines < 10 are skipped.");
+
+        assert_eq!(
+            render_document(&document, 2, |_| true),
+            "<ul class=\"facts\">\n<li><strong>Top &lt;file&gt;:</strong> a &amp; b</li>\n</ul>\n\
+             <h2>People</h2>\n<table class=\"sortable\">\n\
+             <thead><tr><th>Person</th><th>Lines owned</th></tr></thead>\n<tbody>\n\
+             <tr><td>&lt;script&gt;alert(&quot;hi&quot;)&lt;/script&gt;</td><td>3.00*</td></tr>\n\
+             </tbody>\n</table>\n<p class=\"note\">* Lines &lt; 10 are skipped.</p>\n"
+        );
+    }
+
+    #[test]
+    fn it_escapes_closing_tags_in_script_data() {
+        let json = serde_json::to_string(&vec!["</script><script>alert(1)</script>"]).unwrap();
+        let data = script_data(&json);
+        assert!(!data.contains("</"));
+        assert_eq!(data, "[\"<\\/script><script>alert(1)<\\/script>\"]");
+        let parsed: Vec<String> = serde_json::from_str(&data).unwrap();
+        assert_eq!(parsed, vec!["</script><script>alert(1)</script>"]);
+    }
+}
@@ -5,8 +5,25 @@ use std::collections::BTreeMap;
 
 use self::serde::ser::{Serialize, Serializer, SerializeStruct, SerializeMap};
 
+use count::{counts, Count};
 use person::{CombinedTracking, PeopleTracking, TeamTracking};
 use ownership::{OwnershipStatistics, ComputedOwnership};
+use commits::{CommitStatistics, ComputedCommitActivity};
+use activity::{ActivityStatistics, Heatmap};
+use streaks::{StreakStatistics, ComputedStreaks};
+use churn::{ChurnStatistics, ComputedChurn, BigCommit};
+use truck_factor::{TruckFactorReport, DirectoryTruckFactor, TruckFactor};
+use knowledge_loss::{KnowledgeLossReport, DepartedOwner, OrphanedFile, LossShare};
+use codeowners_audit::{CodeOwnersAudit, RuleAudit, UnlistedOwner};
+use line_age::{LineAgeStatistics, OldestLine};
+use messages::{MessageStatistics, Subject};
+use commit_types::CommitTypeStatistics;
+use release_notes::ReleaseNotes;
+use newcomers::{NewcomerReport, Newcomer, FirstCommit, FirstOwnedLines};
+use timeline::Timeline;
+use report::Report;
+use dates;
+use person::Person;
 use errors::*;
 
 // The JSON formatter prints JSON to STDOUT
@@ -37,6 +54,16 @@ where
     }
 }
 
+pub fn write<T, W>(data: &T, out: &mut W) -> Result<()>
+where
+    T: Serialize,
+    W: ::std::io::Write,
+{
+    serde_json::to_writer_pretty(&mut *out, data)?;
+    writeln!(out, "")?;
+    Ok(())
+}
+
 impl<'b> Serialize for OwnershipStatistics<'b> {
     fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
     where
@@ -63,6 +90,248 @@ impl<'b> Serialize for OwnershipStatistics<'b> {
     }
 }
 
+impl<'b> Serialize for CommitStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("CommitStatistics", 3)?;
+
+        let people: BTreeMap<&str, ComputedCommitActivity> = self.people_toplist()
+            .into_iter()
+            .map(|(person, activity)| (person.name(), activity))
+            .collect();
+
+        let teams: BTreeMap<&str, ComputedCommitActivity> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, activity)| {
+                (team_name.unwrap_or("(No team)"), activity)
+            })
+            .collect();
+
+        s.serialize_field("repository", &self.repository_activity())?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+fn matrix_counts(heatmap: &Heatmap) -> Vec<Vec<Count>> {
+    heatmap.matrix().iter().map(|hours| counts(hours)).collect()
+}
+
+impl<'b> Serialize for ActivityStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("ActivityStatistics", 3)?;
+
+        let people: BTreeMap<&str, Vec<Vec<Count>>> = self.people_toplist()
+            .into_iter()
+            .map(|(person, heatmap)| (person.name(), matrix_counts(heatmap)))
+            .collect();
+
+        let teams: BTreeMap<&str, Vec<Vec<Count>>> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, heatmap)| {
+                (team_name.unwrap_or("(No team)"), matrix_counts(heatmap))
+            })
+            .collect();
+
+        s.serialize_field("repository", &matrix_counts(&self.repository))?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for StreakStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("StreakStatistics", 3)?;
+
+        let people: BTreeMap<&str, ComputedStreaks> = self.people_toplist()
+            .into_iter()
+            .map(|(person, streaks)| (person.name(), streaks))
+            .collect();
+
+        let teams: BTreeMap<&str, ComputedStreaks> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, streaks)| {
+                (team_name.unwrap_or("(No team)"), streaks)
+            })
+            .collect();
+
+        s.serialize_field("repository", &self.repository_streaks())?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for ChurnStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("ChurnStatistics", 5)?;
+
+        let people: BTreeMap<&str, ComputedChurn> = self.people_toplist()
+            .into_iter()
+            .map(|(person, churn)| (person.name(), churn))
+            .collect();
+
+        let teams: BTreeMap<&str, ComputedChurn> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, churn)| (team_name.unwrap_or("(No team)"), churn))
+            .collect();
+
+        let net_lines_leaderboard: Vec<&str> = self.net_lines_leaderboard()
+            .into_iter()
+            .map(|(person, _)| person.name())
+            .collect();
+
+        s.serialize_field("repository", &self.repository_churn())?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.serialize_field("net_lines_leaderboard", &net_lines_leaderboard)?;
+        s.serialize_field("biggest_commits", &self.biggest_commits)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for BigCommit<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("BigCommit", 5)?;
+        s.serialize_field("sha", &self.sha)?;
+        s.serialize_field("summary", &self.summary)?;
+        s.serialize_field("person", self.person.name())?;
+        s.serialize_field("insertions", &self.insertions)?;
+        s.serialize_field("deletions", &self.deletions)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for TruckFactorReport<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("TruckFactorReport", 4)?;
+        s.serialize_field("owner_threshold", &self.owner_threshold)?;
+        s.serialize_field("people", &self.people)?;
+        s.serialize_field("teams", &self.teams)?;
+        s.serialize_field("directories", &self.directories)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for DirectoryTruckFactor<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("DirectoryTruckFactor", 3)?;
+        s.serialize_field("path", &self.path)?;
+        s.serialize_field("people", &self.people)?;
+        s.serialize_field("teams", &self.teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for TruckFactor<&'b Person> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let critical: Vec<&str> = self.critical.iter().map(|person| person.name()).collect();
+
+        let mut s = serializer.serialize_struct("TruckFactor", 3)?;
+        s.serialize_field("files", &self.files)?;
+        s.serialize_field("truck_factor", &self.factor())?;
+        s.serialize_field("critical", &critical)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for TruckFactor<Option<&'b str>> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let critical: Vec<&str> = self.critical
+            .iter()
+            .map(|team_name| team_name.unwrap_or("(No team)"))
+            .collect();
+
+        let mut s = serializer.serialize_struct("TruckFactor", 3)?;
+        s.serialize_field("files", &self.files)?;
+        s.serialize_field("truck_factor", &self.factor())?;
+        s.serialize_field("critical", &critical)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for KnowledgeLossReport<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("KnowledgeLossReport", 6)?;
+
+        let teams: BTreeMap<&str, &LossShare> = self.teams
+            .iter()
+            .map(|&(ref team_name, ref loss)| (team_name.unwrap_or("(No team)"), loss))
+            .collect();
+
+        s.serialize_field("threshold", &self.threshold)?;
+        s.serialize_field("repository", &self.repository)?;
+        s.serialize_field("departed", &self.departed)?;
+        s.serialize_field("orphaned_files", &self.orphaned_files)?;
+        s.serialize_field("directories", &self.directories)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for DepartedOwner<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("DepartedOwner", 3)?;
+        s.serialize_field("person", self.person.name())?;
+        s.serialize_field("left", &self.person.left())?;
+        s.serialize_field("lines", &self.lines)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for OrphanedFile<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let former_owners: Vec<&str> = self.former_owners
+            .iter()
+            .map(|person| person.name())
+            .collect();
+
+        let mut s = serializer.serialize_struct("OrphanedFile", 4)?;
+        s.serialize_field("path", &self.path)?;
+        s.serialize_field("lines", &self.lines)?;
+        s.serialize_field("departed_lines", &self.departed_lines)?;
+        s.serialize_field("former_owners", &former_owners)?;
+        s.end()
+    }
+}
+
 impl<'b, T> Serialize for CombinedTracking<'b, T>
 where
     T: Default + Serialize,
@@ -111,3 +380,302 @@ where
         s.end()
     }
 }
+
+impl<'b> Serialize for CodeOwnersAudit<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("CodeOwnersAudit", 5)?;
+        s.serialize_field("path", &self.path)?;
+        s.serialize_field("threshold", &self.threshold)?;
+        s.serialize_field("rules", &self.rules)?;
+        s.serialize_field("unowned_files", &self.unowned_files)?;
+        s.serialize_field("unlisted_owners", &self.unlisted_owners)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for RuleAudit<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let actual_owners: BTreeMap<&str, u32> = self.actual_owners
+            .iter()
+            .map(|&(person, lines)| (person.name(), lines))
+            .collect();
+
+        let mut s = serializer.serialize_struct("RuleAudit", 10)?;
+        s.serialize_field("line", &self.line)?;
+        s.serialize_field("pattern", &self.rule.pattern)?;
+        s.serialize_field("owners", &self.rule.owners)?;
+        s.serialize_field("unknown_owners", &self.unknown_owners)?;
+        s.serialize_field("files", &self.files)?;
+        s.serialize_field("lines", &self.lines)?;
+        s.serialize_field("fraction_owned_by_listed", &self.fraction_owned_by_listed())?;
+        s.serialize_field("actual_owners", &actual_owners)?;
+        s.serialize_field("weak", &self.weak)?;
+        s.serialize_field("unused", &self.unused)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for UnlistedOwner<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("UnlistedOwner", 3)?;
+        s.serialize_field("person", self.person.name())?;
+        s.serialize_field("lines", &self.lines)?;
+        s.serialize_field("fraction_owned", &self.fraction_owned)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for LineAgeStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("LineAgeStatistics", 5)?;
+
+        let directories: BTreeMap<&str, _> = self.directories().into_iter().collect();
+
+        let people: BTreeMap<&str, _> = self.people_toplist()
+            .into_iter()
+            .map(|(person, ages)| (person.name(), ages))
+            .collect();
+
+        let teams: BTreeMap<&str, _> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, ages)| (team_name.unwrap_or("(No team)"), ages))
+            .collect();
+
+        s.serialize_field("repository", &self.repository_ages())?;
+        s.serialize_field("oldest_line", &self.oldest_line)?;
+        s.serialize_field("directories", &directories)?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for OldestLine<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("OldestLine", 6)?;
+        s.serialize_field("path", &self.path)?;
+        s.serialize_field("line_number", &self.line_number)?;
+        s.serialize_field("content", &self.content)?;
+        s.serialize_field("commit", &self.commit.to_string())?;
+        s.serialize_field("date", &dates::format_day(self.day))?;
+        s.serialize_field("person", self.person.name())?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for MessageStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("MessageStatistics", 5)?;
+
+        let people: BTreeMap<&str, _> = self.people_toplist()
+            .into_iter()
+            .map(|(person, messages)| (person.name(), messages))
+            .collect();
+
+        let teams: BTreeMap<&str, _> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, messages)| (team_name.unwrap_or("(No team)"), messages))
+            .collect();
+
+        s.serialize_field("repository", &self.repository_messages())?;
+        s.serialize_field("longest_subjects", &self.longest_subjects)?;
+        s.serialize_field("shortest_subjects", &self.shortest_subjects)?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for Subject<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("Subject", 4)?;
+        s.serialize_field("sha", &self.sha)?;
+        s.serialize_field("person", self.person.name())?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("length", &self.length())?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for CommitTypeStatistics<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("CommitTypeStatistics", 4)?;
+
+        let people: BTreeMap<&str, _> = self.people_toplist()
+            .into_iter()
+            .map(|(person, types)| (person.name(), types))
+            .collect();
+
+        let teams: BTreeMap<&str, _> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, types)| (team_name.unwrap_or("(No team)"), types))
+            .collect();
+
+        s.serialize_field("repository", &self.repository_types())?;
+        s.serialize_field("breaking_changes_by_scope", &self.breaking_changes_by_scope)?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for ReleaseNotes<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("ReleaseNotes", 6)?;
+
+        let people: BTreeMap<&str, _> = self.people_toplist()
+            .into_iter()
+            .map(|(person, contribution)| (person.name(), contribution))
+            .collect();
+
+        let teams: BTreeMap<&str, _> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, contribution)| {
+                (team_name.unwrap_or("(No team)"), contribution)
+            })
+            .collect();
+
+        let mut first_time_contributors: Vec<&str> = self.first_time_contributors
+            .iter()
+            .map(|person| person.name())
+            .collect();
+        first_time_contributors.sort();
+
+        s.serialize_field("from", &self.from)?;
+        s.serialize_field("to", &self.to)?;
+        s.serialize_field("commits", &self.commits)?;
+        s.serialize_field("first_time_contributors", &first_time_contributors)?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for NewcomerReport<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("NewcomerReport", 3)?;
+        s.serialize_field("since", &self.since.map(dates::format_day))?;
+        s.serialize_field("until", &self.until.map(dates::format_day))?;
+        s.serialize_field("newcomers", &self.newcomers)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for Newcomer<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("Newcomer", 5)?;
+        s.serialize_field("name", self.person.name())?;
+        s.serialize_field("joined", &self.joined.map(dates::format_day))?;
+        s.serialize_field("commits", &self.commits)?;
+        s.serialize_field("first_commit", &self.first_commit)?;
+        s.serialize_field("first_owned", &self.first_owned)?;
+        s.end()
+    }
+}
+
+impl Serialize for FirstCommit {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("FirstCommit", 4)?;
+        s.serialize_field("sha", &self.sha)?;
+        s.serialize_field("date", &dates::format_day(self.day))?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("files", &self.files)?;
+        s.end()
+    }
+}
+
+impl Serialize for FirstOwnedLines {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("FirstOwnedLines", 3)?;
+        s.serialize_field("path", &self.path)?;
+        s.serialize_field("date", &dates::format_day(self.day))?;
+        s.serialize_field("lines", &self.lines)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for Timeline<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("Timeline", 4)?;
+
+        let months = self.months();
+
+        let people: BTreeMap<&str, Vec<Count>> = self.people_toplist()
+            .into_iter()
+            .map(|(person, commits)| (person.name(), counts(&commits.series(&months))))
+            .collect();
+
+        let teams: BTreeMap<&str, Vec<Count>> = self.teams_toplist()
+            .into_iter()
+            .map(|(team_name, commits)| {
+                (team_name.unwrap_or("(No team)"), counts(&commits.series(&months)))
+            })
+            .collect();
+
+        let month_names: Vec<String> = months.iter().map(|month| month.to_string()).collect();
+
+        s.serialize_field("months", &month_names)?;
+        s.serialize_field("repository", &counts(&self.repository.series(&months)))?;
+        s.serialize_field("people", &people)?;
+        s.serialize_field("teams", &teams)?;
+        s.end()
+    }
+}
+
+impl<'b> Serialize for Report<'b> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("Report", 6)?;
+        s.serialize_field("head_sha", &self.head_sha)?;
+        s.serialize_field("generated_on", &self.generated_on)?;
+        s.serialize_field("ownership", &self.ownership)?;
+        s.serialize_field("commits", &self.commits)?;
+        s.serialize_field("activity", &self.activity)?;
+        s.serialize_field("timeline", &self.timeline)?;
+        s.end()
+    }
+}
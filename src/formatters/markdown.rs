@@ -0,0 +1,124 @@
+use super::document::{Document, ToDocument};
+use errors::*;
+
+// The Markdown formatter prints GitHub-flavored Markdown to STDOUT, ready to paste into a PR or
+// a wiki page.
+pub struct Formatter {}
+
+pub trait Format {
+    fn format(&self) -> Result<()>;
+}
+
+impl Formatter {
+    pub fn display<F>(data: F) -> Result<()>
+    where
+        F: Format,
+    {
+        data.format()
+    }
+}
+
+impl<T> Format for T
+where
+    T: ToDocument,
+{
+    fn format(&self) -> Result<()> {
+        print!("{}", render(&self.to_document()));
+        Ok(())
+    }
+}
+
+/// Escapes the characters Markdown would otherwise read as formatting, links or HTML, so paths
+/// like `src/__init__.py` and values like `12.50*` show up as they are. Newlines are replaced, as
+/// they would end a table row.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '<' | '>' | '[' | ']' | '|' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn render(document: &Document) -> String {
+    let mut markdown = format!("# {}\n", escape(&document.title));
+
+    if !document.facts.is_empty() {
+        markdown.push('\n');
+        for fact in &document.facts {
+            markdown.push_str(&format!(
+                "- **{}:** {}\n",
+                escape(&fact.label),
+                escape(&fact.value)
+            ));
+        }
+    }
+
+    for section in &document.sections {
+        markdown.push_str(&format!("\n## {}\n\n", escape(&section.title)));
+        if section.rows.is_empty() {
+            markdown.push_str("_None_\n");
+            continue;
+        }
+
+        let columns = section.shown_columns();
+        let header: Vec<String> = columns.iter().map(|column| escape(&column.title)).collect();
+        markdown.push_str(&format!("| {} |\n", header.join(" | ")));
+        let separator: Vec<&str> = columns.iter().map(|_| "---").collect();
+        markdown.push_str(&format!("| {} |\n", separator.join(" | ")));
+        for row in &section.rows {
+            let cells: Vec<String> = section
+                .shown_cells(row)
+                .iter()
+                .map(|cell| escape(&cell.text))
+                .collect();
+            markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+
+    for note in &document.notes {
+        markdown.push_str(&format!("\n{}\n", escape(note)));
+    }
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_sections_as_tables() {
+        let mut document = Document::new("Hotspots");
+        document.fact("Files", 2);
+        document
+            .section("Files", "file", vec!["File", "Commits"])
+            .row(vec![String::from("a|b.rs"), String::from("3")]);
+        document.section("Empty", "file", vec!["File"]);
+        document.note("Most changed first.");
+
+        assert_eq!(
+            render(&document),
+            "# Hotspots\n\n- **Files:** 2\n\n## Files\n\n| File | Commits |\n| --- | --- |\n\
+             | a\\|b.rs | 3 |\n\n## Empty\n\n_None_\n\nMost changed first.\n"
+        );
+    }
+
+    #[test]
+    fn it_escapes_markdown_syntax() {
+        assert_eq!(escape("src/__init__.py"), "src/\\_\\_init\\_\\_.py");
+        assert_eq!(escape("12.50*"), "12.50\\*");
+        assert_eq!(escape("<script> `code` [link]"), "\\<script\\> \\`code\\` \\[link\\]");
+        assert_eq!(escape("a\nb"), "a b");
+
+        let mut document = Document::new("Ownership");
+        document.note("* Weighted");
+        assert!(render(&document).ends_with("\n\\* Weighted\n"));
+    }
+}
@@ -1,33 +1,82 @@
 mod json;
+mod yaml;
 mod console;
+mod markdown;
+mod delimited;
+mod html;
+mod svg;
+mod document;
+mod charts;
+mod text;
+
+use std::io::Write;
 
 use clap::ArgMatches;
 use super::errors::*;
+use report::Report;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Format {
     Console,
     JSON,
+    YAML,
+    Markdown,
+    CSV,
+    TSV,
+    HTML,
+    SVG,
 }
 
 impl Format {
     pub fn display<F>(&self, data: F) -> Result<()>
     where
-        F: json::Format + console::Format,
+        F: console::Format
+            + json::Format
+            + yaml::Format
+            + markdown::Format
+            + delimited::Format
+            + html::Format
+            + svg::Format,
     {
         match *self {
             Format::Console => console::Formatter::display(data),
             Format::JSON => json::Formatter::display(data),
+            Format::YAML => yaml::Formatter::display(data),
+            Format::Markdown => markdown::Formatter::display(data),
+            Format::CSV => delimited::Formatter::display(data, delimited::Separator::Comma),
+            Format::TSV => delimited::Formatter::display(data, delimited::Separator::Tab),
+            Format::HTML => html::Formatter::display(data),
+            Format::SVG => svg::Formatter::display(data),
+        }
+    }
+
+    /// Writes the combined report, which only makes sense as a page or as data.
+    pub fn write_report<W>(&self, report: &Report, out: &mut W) -> Result<()>
+    where
+        W: Write,
+    {
+        match *self {
+            Format::HTML => html::write_report(report, out),
+            Format::JSON => json::write(report, out),
+            Format::YAML => yaml::write(report, out),
+            _ => bail!("The report can only be written as HTML, JSON or YAML."),
         }
     }
 }
 
-pub static POSSIBLE_VALUES: &'static [&'static str] = &["console", "json"];
+pub static POSSIBLE_VALUES: &'static [&'static str] =
+    &["console", "json", "yaml", "markdown", "csv", "tsv", "html", "svg"];
 
 pub fn from_args(args: &ArgMatches) -> Result<Format> {
     match args.value_of("format") {
         Some("console") | None => Ok(Format::Console),
         Some("json") => Ok(Format::JSON),
+        Some("yaml") => Ok(Format::YAML),
+        Some("markdown") => Ok(Format::Markdown),
+        Some("csv") => Ok(Format::CSV),
+        Some("tsv") => Ok(Format::TSV),
+        Some("html") => Ok(Format::HTML),
+        Some("svg") => Ok(Format::SVG),
         Some(other) => bail!("Not a valid format: {}", other),
     }
 }
@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+
+use ownership::OwnershipStatistics;
+use commits::CommitStatistics;
+use activity::ActivityStatistics;
+use streaks::StreakStatistics;
+use churn::ChurnStatistics;
+use hotspots::HotspotStatistics;
+use truck_factor::TruckFactorReport;
+use knowledge_loss::KnowledgeLossReport;
+use codeowners::CodeOwners;
+use codeowners_audit::CodeOwnersAudit;
+use line_age::LineAgeStatistics;
+use collaboration::CollaborationGraph;
+use coupling::CouplingStatistics;
+use messages::MessageStatistics;
+use commit_types::CommitTypeStatistics;
+use release_notes::ReleaseNotes;
+use newcomers::NewcomerReport;
+use timeline::Timeline;
+use person::NO_TEAM_NAME;
+use super::charts::{self, Chart, Series, Slice};
+use errors::*;
+
+// The SVG formatter prints a chart to STDOUT, small enough to commit and embed in a README.
+pub struct Formatter {}
+
+pub trait Format {
+    fn format(&self) -> Result<()> {
+        bail!(
+            "This report can not be drawn as SVG yet. Charts can be drawn for ownership, \
+             commits, activity, churn, line-age, commit-types, release-notes and timeline."
+        )
+    }
+}
+
+impl Formatter {
+    pub fn display<F>(data: F) -> Result<()>
+    where
+        F: Format,
+    {
+        data.format()
+    }
+}
+
+fn print(chart: &Chart) -> Result<()> {
+    println!("{}", chart.to_svg());
+    Ok(())
+}
+
+fn slice(name: &str, value: f64) -> Slice {
+    Slice {
+        name: name.to_owned(),
+        value: value,
+    }
+}
+
+fn team_name(team_name: Option<&str>) -> &str {
+    team_name.unwrap_or(NO_TEAM_NAME)
+}
+
+impl<'a, 'b> Format for &'a OwnershipStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        let people = self.people_toplist()
+            .into_iter()
+            .map(|(person, score)| slice(person.name(), score.total_lines_owned))
+            .collect();
+        let teams = self.teams_toplist()
+            .into_iter()
+            .map(|(name, score)| slice(team_name(name), score.total_lines_owned))
+            .collect();
+
+        print(&charts::side_by_side(vec![
+            charts::pie_chart("Lines owned by people", people),
+            charts::pie_chart("Lines owned by teams", teams),
+        ]))
+    }
+}
+
+impl<'a, 'b> Format for &'a CommitStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        let people = self.people_toplist()
+            .into_iter()
+            .map(|(person, activity)| slice(person.name(), activity.commits))
+            .collect();
+        let teams = self.teams_toplist()
+            .into_iter()
+            .map(|(name, activity)| slice(team_name(name), activity.commits))
+            .collect();
+
+        print(&charts::side_by_side(vec![
+            charts::pie_chart("Commits by people", people),
+            charts::pie_chart("Commits by teams", teams),
+        ]))
+    }
+}
+
+impl<'a, 'b> Format for &'a ActivityStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        print(&charts::heatmap(&self.repository))
+    }
+}
+
+impl<'a, 'b> Format for &'a LineAgeStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        let lines_per_year = self.repository_ages().lines_per_year;
+
+        // Years without any surviving lines are still part of the timeline.
+        let mut labels = Vec::new();
+        let mut values = Vec::new();
+        let first = lines_per_year.keys().next().cloned();
+        let last = lines_per_year.keys().next_back().cloned();
+        if let (Some(first), Some(last)) = (first, last) {
+            for year in first..last + 1 {
+                labels.push(year.to_string());
+                values.push(lines_per_year.get(&year).cloned().unwrap_or(0.0));
+            }
+        }
+
+        let series = vec![
+            Series {
+                name: String::from("Surviving lines by year of last change"),
+                values: values,
+            },
+        ];
+        print(&charts::line_chart(&labels, &series))
+    }
+}
+
+impl<'a, 'b> Format for &'a ReleaseNotes<'b> {
+    fn format(&self) -> Result<()> {
+        let people = self.people_toplist()
+            .into_iter()
+            .map(|(person, contribution)| slice(person.name(), contribution.commits))
+            .collect();
+        let teams = self.teams_toplist()
+            .into_iter()
+            .map(|(name, contribution)| slice(team_name(name), contribution.commits))
+            .collect();
+
+        print(&charts::side_by_side(vec![
+            charts::pie_chart(&format!("Commits in {}..{}", self.from, self.to), people),
+            charts::pie_chart("Commits by teams", teams),
+        ]))
+    }
+}
+
+impl<'a, 'b> Format for &'a Timeline<'b> {
+    fn format(&self) -> Result<()> {
+        print(&charts::timeline(self))
+    }
+}
+
+impl<'a, 'b> Format for &'a ChurnStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        let people = self.people_toplist()
+            .into_iter()
+            .map(|(person, churn)| slice(person.name(), churn.changed_lines()))
+            .collect();
+        let teams = self.teams_toplist()
+            .into_iter()
+            .map(|(name, churn)| slice(team_name(name), churn.changed_lines()))
+            .collect();
+
+        print(&charts::side_by_side(vec![
+            charts::pie_chart("Lines changed by people", people),
+            charts::pie_chart("Lines changed by teams", teams),
+        ]))
+    }
+}
+
+impl<'a, 'b> Format for &'a CommitTypeStatistics<'b> {
+    fn format(&self) -> Result<()> {
+        let mut types: Vec<(String, f64)> = self.repository_types().types.into_iter().collect();
+        // Note: Reverse sort
+        types.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        let types = types
+            .into_iter()
+            .map(|(kind, commits)| slice(&kind, commits))
+            .collect();
+
+        print(&charts::pie_chart("Commit types", types))
+    }
+}
+
+impl<'a, 'b> Format for &'a StreakStatistics<'b> {}
+impl<'a> Format for &'a HotspotStatistics {}
+impl<'a, 'b> Format for &'a TruckFactorReport<'b> {}
+impl<'a, 'b> Format for &'a KnowledgeLossReport<'b> {}
+impl<'a> Format for &'a CodeOwners {}
+impl<'a, 'b> Format for &'a CodeOwnersAudit<'b> {}
+impl<'a> Format for &'a CollaborationGraph {}
+impl<'a> Format for &'a CouplingStatistics {}
+impl<'a, 'b> Format for &'a MessageStatistics<'b> {}
+impl<'a, 'b> Format for &'a NewcomerReport<'b> {}
@@ -0,0 +1,86 @@
+//! Formatting of values that the console tables and documents share.
+
+use std::cmp::Ordering;
+
+use ownership::ComputedOwnership;
+use streaks::Span;
+use commit_types::ComputedTypes;
+use timeline::MonthlyCommits;
+use person::{Person, NO_TEAM_NAME};
+
+/// People without a team are grouped together as "(Others)".
+pub fn team_name(team_name: Option<&str>) -> String {
+    team_name.unwrap_or(NO_TEAM_NAME).to_owned()
+}
+
+pub fn team_names(team_names: &[Option<&str>]) -> String {
+    team_names
+        .iter()
+        .map(|name| team_name(*name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn people_names(people: &[&Person]) -> String {
+    people.iter().map(|person| person.name()).collect::<Vec<_>>().join(", ")
+}
+
+pub fn format_count(count: f64) -> String {
+    if count.fract() == 0.0 {
+        format!("{:.0}", count)
+    } else {
+        format!("{:.2}", count)
+    }
+}
+
+pub fn format_net_lines(lines: f64) -> String {
+    if lines.fract() == 0.0 {
+        format!("{:+.0}", lines)
+    } else {
+        format!("{:+.2}", lines)
+    }
+}
+
+pub fn format_lines_owned(score: &ComputedOwnership) -> String {
+    if score.weighted {
+        format!("{:.2}*", score.total_lines_owned)
+    } else {
+        format!("{:.0}", score.total_lines_owned)
+    }
+}
+
+pub fn format_span(span: &Option<Span>) -> String {
+    match *span {
+        Some(ref span) => format!("{} days ({} – {})", span.days, span.from, span.to),
+        None => String::from("-"),
+    }
+}
+
+pub fn format_age(days: i64) -> String {
+    if days >= 365 {
+        format!("{:.1} years", days as f64 / 365.25)
+    } else {
+        format!("{} days", days)
+    }
+}
+
+pub fn format_other_types(types: &ComputedTypes) -> String {
+    let mut others: Vec<(&String, &f64)> = types
+        .types
+        .iter()
+        .filter(|&(kind, _)| kind != "feat" && kind != "fix")
+        .collect();
+    others.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(Ordering::Equal)); // Note: Reverse sort
+    others
+        .iter()
+        .map(|&(kind, count)| format!("{} {}", kind, format_count(*count)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn format_busiest_month(commits: &MonthlyCommits) -> String {
+    commits
+        .busiest_month()
+        .map(|(month, commits)| format!("{} ({} commits)", month, format_count(commits)))
+        .unwrap_or_else(|| String::from("-"))
+}
@@ -0,0 +1,46 @@
+extern crate serde;
+extern crate serde_yaml;
+
+use std::io::Write;
+
+use self::serde::ser::Serialize;
+
+use errors::*;
+
+// The YAML formatter prints the same data as the JSON formatter, as YAML, to STDOUT.
+pub struct Formatter {}
+
+pub trait Format {
+    fn format(&self) -> Result<()>;
+}
+
+impl Formatter {
+    pub fn display<F>(data: F) -> Result<()>
+    where
+        F: Format,
+    {
+        data.format().and_then(|_| {
+            println!("");
+            Ok(())
+        })
+    }
+}
+
+impl<T> Format for T
+where
+    T: Serialize,
+{
+    fn format(&self) -> Result<()> {
+        serde_yaml::to_writer(::std::io::stdout(), self).map_err(|e| e.into())
+    }
+}
+
+pub fn write<T, W>(data: &T, out: &mut W) -> Result<()>
+where
+    T: Serialize,
+    W: Write,
+{
+    serde_yaml::to_writer(&mut *out, data)?;
+    writeln!(out, "")?;
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::{Commit, Delta, DiffFindOptions, ObjectType, Oid, Repository, Revwalk};
+
+use super::errors::*;
+use dates;
+
+/// Limits which part of the history is looked at. Days are inclusive.
+#[derive(Debug, Default, Clone)]
+pub struct Selection {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// Revisions and ranges like `git log` takes them: `v1.0..v2.0`, `main...feature` or a
+    /// revision with any number of `^excluded` ones. The history of HEAD is used when empty.
+    pub revisions: Vec<String>,
+}
+
+impl Selection {
+    /// The same revisions, without limiting the dates.
+    pub fn without_dates(&self) -> Selection {
+        Selection {
+            since: None,
+            until: None,
+            revisions: self.revisions.clone(),
+        }
+    }
+
+    pub fn includes_day(&self, day: i64) -> bool {
+        self.since.map_or(true, |since| day >= since) &&
+            self.until.map_or(true, |until| day <= until)
+    }
+
+    /// Checks the day the commit was authored on.
+    pub fn includes(&self, commit: &Commit) -> bool {
+        self.includes_day(dates::local_day(&commit.author().when()))
+    }
+}
+
+/// Iterates over the commits in the history of a repository, newest first.
+pub struct CommitWalker<'repo> {
+    repo: &'repo Repository,
+    revwalk: Revwalk<'repo>,
+    selection: Selection,
+}
+
+impl<'repo> CommitWalker<'repo> {
+    /// Walks the revisions of the selection, leaving out commits outside of its dates.
+    pub fn new(repo: &'repo Repository, selection: &Selection) -> Result<CommitWalker<'repo>> {
+        let mut revwalk = repo.revwalk()?;
+        if selection.revisions.is_empty() {
+            revwalk.push_head()?;
+        }
+        for revision in &selection.revisions {
+            push_revision(repo, &mut revwalk, revision).chain_err(|| {
+                ErrorKind::InvalidRevision(revision.clone())
+            })?;
+        }
+
+        Ok(CommitWalker {
+            repo: repo,
+            revwalk: revwalk,
+            selection: selection.clone(),
+        })
+    }
+
+    /// Hides the given commit and all of its ancestors from the walk.
+    pub fn hide(&mut self, oid: Oid) -> Result<()> {
+        self.revwalk.hide(oid).map_err(|e| e.into())
+    }
+}
+
+fn push_revision(repo: &Repository, revwalk: &mut Revwalk, revision: &str) -> Result<()> {
+    if revision.starts_with('^') {
+        revwalk.hide(resolve_commit(repo, &revision[1..])?)?;
+    } else if let Some(dots) = revision.find("...") {
+        // Commits reachable from either side, but not from both.
+        let from = resolve_commit(repo, or_head(&revision[0..dots]))?;
+        let to = resolve_commit(repo, or_head(&revision[dots + 3..]))?;
+        revwalk.push(from)?;
+        revwalk.push(to)?;
+        if let Ok(merge_base) = repo.merge_base(from, to) {
+            revwalk.hide(merge_base)?;
+        }
+    } else if revision.contains("..") {
+        revwalk.push_range(revision)?;
+    } else {
+        revwalk.push(resolve_commit(repo, revision)?)?;
+    }
+    Ok(())
+}
+
+/// The commit a selection ends at, which is HEAD unless other revisions are given. Selections
+/// ending at more than one commit, like `main...feature` or `main feature`, have no single tip.
+pub fn tip(repo: &Repository, selection: &Selection) -> Result<Option<Oid>> {
+    if selection.revisions.is_empty() {
+        return resolve_commit(repo, "HEAD").map(Some);
+    }
+
+    let mut tips = Vec::new();
+    for revision in &selection.revisions {
+        if revision.starts_with('^') {
+            continue;
+        } else if revision.contains("...") {
+            return Ok(None);
+        } else if let Some(dots) = revision.find("..") {
+            tips.push(or_head(&revision[dots + 2..]));
+        } else {
+            tips.push(revision.as_str());
+        }
+    }
+
+    if tips.len() == 1 {
+        let revision = tips[0];
+        let oid = resolve_commit(repo, revision).chain_err(|| {
+            ErrorKind::InvalidRevision(revision.to_owned())
+        })?;
+        Ok(Some(oid))
+    } else {
+        Ok(None)
+    }
+}
+
+fn or_head(revision: &str) -> &str {
+    if revision.is_empty() { "HEAD" } else { revision }
+}
+
+/// Finds the commit a revision like a branch, tag or abbreviated SHA points at.
+pub fn resolve_commit(repo: &Repository, revision: &str) -> Result<Oid> {
+    Ok(repo.revparse_single(revision)?.peel(ObjectType::Commit)?.id())
+}
+
+impl<'repo> Iterator for CommitWalker<'repo> {
+    type Item = Commit<'repo>;
+
+    fn next(&mut self) -> Option<Commit<'repo>> {
+        // Commits that cannot be read are skipped instead of aborting the whole walk.
+        loop {
+            match self.revwalk.next() {
+                Some(Ok(oid)) => {
+                    if let Ok(commit) = self.repo.find_commit(oid) {
+                        if self.selection.includes(&commit) {
+                            return Some(commit);
+                        }
+                    }
+                }
+                Some(Err(_)) => {}
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Merges only repeat changes that were already made in the merged commits, so reports that
+/// count changes skip them to avoid counting the same work twice.
+pub fn is_merge(commit: &Commit) -> bool {
+    commit.parent_count() > 1
+}
+
+/// A file that was changed in a commit.
+#[derive(Debug)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// The previous path of the file, if it was renamed in the commit.
+    pub renamed_from: Option<PathBuf>,
+}
+
+/// Lists the files that a commit changed compared to its first parent, with renames detected.
+pub fn changed_files(repo: &Repository, commit: &Commit) -> Result<Vec<FileChange>> {
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+    let tree = commit.tree()?;
+
+    let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let changes = diff.deltas()
+        .filter_map(|delta| {
+            let file = match delta.status() {
+                Delta::Deleted => delta.old_file(),
+                _ => delta.new_file(),
+            };
+            let renamed_from = match delta.status() {
+                Delta::Renamed => delta.old_file().path().map(Path::to_path_buf),
+                _ => None,
+            };
+            file.path().map(|path| {
+                FileChange {
+                    path: path.to_path_buf(),
+                    renamed_from: renamed_from,
+                }
+            })
+        })
+        .collect();
+
+    Ok(changes)
+}
+
+/// Keeps track of renamed files while walking history backwards, so that changes made to a file
+/// before it was renamed can be attributed to the path it has today.
+#[derive(Debug, Default)]
+pub struct RenameTracker {
+    current_paths: HashMap<PathBuf, PathBuf>,
+}
+
+impl RenameTracker {
+    pub fn new() -> RenameTracker {
+        RenameTracker::default()
+    }
+
+    /// Returns the path the file has in the newest commit seen so far.
+    pub fn current_path(&self, path: &Path) -> PathBuf {
+        self.current_paths.get(path).cloned().unwrap_or_else(
+            || path.to_path_buf(),
+        )
+    }
+
+    /// Reads a change from a commit and returns the current path of the changed file. If the
+    /// change was a rename, older commits touching the old path will be attributed to it too.
+    pub fn follow(&mut self, change: &FileChange) -> PathBuf {
+        let current_path = self.current_path(&change.path);
+        if let Some(ref old_path) = change.renamed_from {
+            self.current_paths.insert(old_path.clone(), current_path.clone());
+        }
+        current_path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    fn change(path: &str, renamed_from: Option<&str>) -> FileChange {
+        FileChange {
+            path: PathBuf::from(path),
+            renamed_from: renamed_from.map(PathBuf::from),
+        }
+    }
+
+    #[test]
+    fn it_follows_renames_back_to_the_current_path() {
+        let mut renames = RenameTracker::new();
+        // Newest first, like the commits are walked.
+        assert_eq!(renames.follow(&change("c.rs", Some("b.rs"))), PathBuf::from("c.rs"));
+        assert_eq!(renames.follow(&change("b.rs", None)), PathBuf::from("c.rs"));
+        assert_eq!(renames.follow(&change("b.rs", Some("a.rs"))), PathBuf::from("c.rs"));
+        assert_eq!(renames.follow(&change("a.rs", None)), PathBuf::from("c.rs"));
+        assert_eq!(renames.follow(&change("other.rs", None)), PathBuf::from("other.rs"));
+    }
+
+    /// master: base - v1 - newer, with a side branch from v1.
+    fn branched_repo() -> TestRepo {
+        let mut test_repo = TestRepo::new();
+        let base = test_repo.commit(&[], &[("a.txt", "1\n")], "Base");
+        let v1 = test_repo.commit(&[base], &[("a.txt", "2\n")], "V1");
+        let newer = test_repo.commit(&[v1], &[("a.txt", "3\n")], "Newer");
+        let side = test_repo.commit(&[v1], &[("b.txt", "b\n")], "Side");
+        test_repo.set_ref("refs/heads/master", newer);
+        test_repo.set_ref("refs/heads/side", side);
+        test_repo.set_ref("refs/tags/v1", v1);
+        test_repo
+    }
+
+    fn walk(test_repo: &TestRepo, revisions: &[&str]) -> Vec<String> {
+        let selection = Selection {
+            revisions: revisions.iter().map(|revision| revision.to_string()).collect(),
+            ..Selection::default()
+        };
+        let mut summaries: Vec<String> = CommitWalker::new(&test_repo.repo, &selection)
+            .unwrap()
+            .map(|commit| commit.summary().unwrap().to_owned())
+            .collect();
+        summaries.sort();
+        summaries
+    }
+
+    #[test]
+    fn it_walks_revisions_and_ranges() {
+        let test_repo = branched_repo();
+
+        assert_eq!(walk(&test_repo, &[]), vec!["Base", "Newer", "V1"]);
+        assert_eq!(walk(&test_repo, &["side"]), vec!["Base", "Side", "V1"]);
+        assert_eq!(walk(&test_repo, &["master", "^v1"]), vec!["Newer"]);
+        assert_eq!(walk(&test_repo, &["master", "side", "^v1"]), vec!["Newer", "Side"]);
+        assert_eq!(walk(&test_repo, &["v1..side"]), vec!["Side"]);
+        assert_eq!(walk(&test_repo, &["master...side"]), vec!["Newer", "Side"]);
+    }
+
+    #[test]
+    fn it_uses_head_for_empty_sides_of_ranges() {
+        let test_repo = branched_repo();
+
+        assert_eq!(walk(&test_repo, &["v1.."]), vec!["Newer"]);
+        assert_eq!(walk(&test_repo, &["..side"]), vec!["Side"]);
+        assert_eq!(walk(&test_repo, &["...side"]), vec!["Newer", "Side"]);
+        assert_eq!(walk(&test_repo, &["side..."]), vec!["Newer", "Side"]);
+    }
+
+    #[test]
+    fn it_finds_the_tip_of_a_selection() {
+        let test_repo = branched_repo();
+        let repo = &test_repo.repo;
+        let tip = |revisions: &[&str]| {
+            let selection = Selection {
+                revisions: revisions.iter().map(|revision| revision.to_string()).collect(),
+                ..Selection::default()
+            };
+            tip(repo, &selection).unwrap()
+        };
+        let commit = |revision| Some(resolve_commit(repo, revision).unwrap());
+
+        assert_eq!(tip(&[]), commit("master"));
+        assert_eq!(tip(&["side", "^v1"]), commit("side"));
+        assert_eq!(tip(&["v1..side"]), commit("side"));
+        assert_eq!(tip(&["v1.."]), commit("master"));
+        assert_eq!(tip(&["master...side"]), None);
+        assert_eq!(tip(&["master", "side"]), None);
+    }
+
+    #[test]
+    fn it_detects_merges() {
+        let mut test_repo = TestRepo::new();
+        let base = test_repo.commit(&[], &[("a.txt", "a\n")], "Base");
+        let side = test_repo.commit(&[base], &[("a.txt", "a\n"), ("b.txt", "b\n")], "Side");
+        let merge = test_repo.commit(&[base, side], &[("a.txt", "a\n"), ("b.txt", "b\n")], "Merge");
+
+        let repo = &test_repo.repo;
+        assert!(!is_merge(&repo.find_commit(side).unwrap()));
+        assert!(is_merge(&repo.find_commit(merge).unwrap()));
+    }
+}
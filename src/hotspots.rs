@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::errors::*;
+use super::{TreeWalker, Context};
+use history::{self, CommitWalker, RenameTracker, Selection};
+use person::Person;
+
+#[derive(Debug, Serialize)]
+pub struct Hotspot {
+    pub path: String,
+    pub commits: u32,
+    pub authors: usize,
+    pub lines: usize,
+    /// Number of commits times the number of lines; big files that change often are the most
+    /// likely to hide bugs.
+    pub score: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HotspotStatistics {
+    pub files: Vec<Hotspot>,
+}
+
+#[derive(Debug, Default)]
+struct FileHistory<'context> {
+    commits: u32,
+    authors: HashSet<&'context Person>,
+}
+
+pub fn calculate(context: &Context, selection: &Selection, limit: usize) -> Result<HotspotStatistics> {
+    let people_db = context.people_db();
+    let repo = context.repo();
+    let head_commit = context.head_commit()?;
+
+    let mut renames = RenameTracker::new();
+    let mut histories: HashMap<PathBuf, FileHistory> = HashMap::new();
+
+    for commit in CommitWalker::new(repo, &selection.without_dates())? {
+        if history::is_merge(&commit) {
+            continue;
+        }
+
+        let changes = history::changed_files(repo, &commit)?;
+        // Renames are followed outside of the selected dates too, or older changes would be lost.
+        let paths: Vec<PathBuf> = changes.iter().map(|change| renames.follow(change)).collect();
+        if !selection.includes(&commit) {
+            continue;
+        }
+
+        let person = people_db.find_by_signature(commit.author())?;
+        for path in paths {
+            let file_history = histories.entry(path).or_insert_with(Default::default);
+            file_history.commits += 1;
+            file_history.authors.insert(person);
+        }
+    }
+
+    let mut files: Vec<Hotspot> = TreeWalker::new(repo, head_commit.tree()?)
+        .filter(|entry| entry.is_file())
+        .filter_map(|entry| {
+            match (histories.get(entry.path()), entry.blob(repo)) {
+                (Some(file_history), Some(ref blob)) if !blob.is_binary() => {
+                    let lines = count_lines(blob.content());
+                    Some(Hotspot {
+                        path: entry.path().to_string_lossy().into_owned(),
+                        commits: file_history.commits,
+                        authors: file_history.authors.len(),
+                        lines: lines,
+                        score: file_history.commits as u64 * lines as u64,
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    files.sort_by(|a, b| b.score.cmp(&a.score)); // Note: Reverse sort
+    files.truncate(limit);
+
+    Ok(HotspotStatistics { files: files })
+}
+
+/// Counts lines like an editor would, so a last line without a trailing newline counts too.
+fn count_lines(content: &[u8]) -> usize {
+    let newlines = content.iter().filter(|&&byte| byte == b'\n').count();
+    match content.last() {
+        Some(&b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_repo::TestRepo;
+
+    #[test]
+    fn it_counts_lines() {
+        assert_eq!(count_lines(b""), 0);
+        assert_eq!(count_lines(b"one\n"), 1);
+        assert_eq!(count_lines(b"one\ntwo"), 2);
+        assert_eq!(count_lines(b"one\n\nthree\n"), 3);
+    }
+
+    #[test]
+    fn it_ranks_files_and_follows_renames() {
+        let mut test_repo = TestRepo::new();
+        let first = test_repo.commit(
+            &[],
+            &[("old.txt", "1\n2\n"), ("small.txt", "x\n"), ("big.txt", &"line\n".repeat(10))],
+            "Add files",
+        );
+        let renamed = test_repo.commit(
+            &[first],
+            &[("new.txt", "1\n2\n"), ("small.txt", "x\n"), ("big.txt", &"line\n".repeat(10))],
+            "Rename old.txt",
+        );
+        let changed = test_repo.commit(
+            &[renamed],
+            &[("new.txt", "1\n2\n3\n"), ("small.txt", "x\n"), ("big.txt", &"line\n".repeat(10))],
+            "Change new.txt",
+        );
+        test_repo.set_ref("refs/heads/master", changed);
+
+        let statistics = calculate(&test_repo.context(), &Selection::default(), 10).unwrap();
+        let files: Vec<(&str, u32, usize, u64)> = statistics
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.commits, file.lines, file.score))
+            .collect();
+        assert_eq!(
+            files,
+            vec![("big.txt", 1, 10, 10), ("new.txt", 3, 3, 9), ("small.txt", 1, 1, 1)]
+        );
+    }
+}
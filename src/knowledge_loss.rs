@@ -0,0 +1,307 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use super::errors::*;
+use count;
+use ownership::FileOwnership;
+use person::Person;
+use tree_walker::directory_at_depth;
+
+#[derive(Debug, Default, Serialize)]
+pub struct LossShare {
+    #[serde(serialize_with = "count::serialize")]
+    pub total_lines: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub departed_lines: f64,
+}
+
+impl LossShare {
+    fn add_lines(&mut self, lines: f64, departed: bool) {
+        self.total_lines += lines;
+        if departed {
+            self.departed_lines += lines;
+        }
+    }
+
+    pub fn fraction_departed(&self) -> f64 {
+        if self.total_lines > 0.0 {
+            self.departed_lines / self.total_lines
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DepartedOwner<'context> {
+    pub person: &'context Person,
+    pub lines: u32,
+}
+
+/// A file where most lines are owned by people that have left.
+#[derive(Debug)]
+pub struct OrphanedFile<'context> {
+    pub path: String,
+    pub lines: u32,
+    pub departed_lines: u32,
+    pub former_owners: Vec<&'context Person>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DirectoryLoss {
+    pub path: String,
+    pub loss: LossShare,
+}
+
+#[derive(Debug)]
+pub struct KnowledgeLossReport<'context> {
+    pub threshold: f64,
+    pub repository: LossShare,
+    pub departed: Vec<DepartedOwner<'context>>,
+    pub orphaned_files: Vec<OrphanedFile<'context>>,
+    pub directories: Vec<DirectoryLoss>,
+    pub teams: Vec<(Option<&'context str>, LossShare)>,
+}
+
+/// Finds out how much of the code is still owned by people that have left at the given day.
+///
+/// Files where more than `threshold` of the lines are owned by departed people are reported as
+/// orphaned, and directories are grouped `depth` levels deep.
+pub fn calculate<'context>(
+    files: &[FileOwnership<'context>],
+    today: i64,
+    threshold: f64,
+    depth: usize,
+) -> Result<KnowledgeLossReport<'context>> {
+    let mut departed_cache: HashMap<&Person, bool> = HashMap::new();
+
+    let mut repository = LossShare::default();
+    let mut departed_lines: HashMap<&Person, u32> = HashMap::new();
+    let mut orphaned_files = Vec::new();
+    let mut directories: BTreeMap<String, LossShare> = BTreeMap::new();
+    let mut teams: HashMap<Option<&str>, LossShare> = HashMap::new();
+
+    for file in files {
+        let directory = directories
+            .entry(directory_at_depth(&file.path, depth))
+            .or_insert_with(LossShare::default);
+        let mut file_loss = LossShare::default();
+
+        for hunk in &file.hunks {
+            let departed = match departed_cache.get(hunk.person) {
+                Some(&departed) => departed,
+                None => {
+                    let departed = hunk.person.has_left(today)?;
+                    departed_cache.insert(hunk.person, departed);
+                    departed
+                }
+            };
+            let lines = hunk.lines as f64;
+
+            repository.add_lines(lines, departed);
+            directory.add_lines(lines, departed);
+            file_loss.add_lines(lines, departed);
+
+            let memberships = hunk.person.team_memberships();
+            if memberships.is_empty() {
+                teams.entry(None).or_insert_with(LossShare::default).add_lines(
+                    lines,
+                    departed,
+                );
+            }
+            for (team_name, weight) in memberships {
+                teams
+                    .entry(Some(team_name))
+                    .or_insert_with(LossShare::default)
+                    .add_lines(lines * weight, departed);
+            }
+
+            if departed {
+                *departed_lines.entry(hunk.person).or_insert(0) += hunk.lines;
+            }
+        }
+
+        if file_loss.departed_lines > 0.0 && file_loss.fraction_departed() > threshold {
+            let mut former_owners: Vec<(&Person, u32)> = file.lines_per_person()
+                .into_iter()
+                .filter(|&(person, _)| departed_cache.get(person) == Some(&true))
+                .collect();
+            former_owners.sort_by(|a, b| b.1.cmp(&a.1)); // Note: Reverse sort
+
+            orphaned_files.push(OrphanedFile {
+                path: file.path.to_string_lossy().into_owned(),
+                lines: file_loss.total_lines as u32,
+                departed_lines: file_loss.departed_lines as u32,
+                former_owners: former_owners.into_iter().map(|(person, _)| person).collect(),
+            });
+        }
+    }
+
+    let mut departed: Vec<DepartedOwner> = departed_lines
+        .into_iter()
+        .map(|(person, lines)| {
+            DepartedOwner {
+                person: person,
+                lines: lines,
+            }
+        })
+        .collect();
+    departed.sort_by(|a, b| b.lines.cmp(&a.lines)); // Note: Reverse sort
+
+    orphaned_files.sort_by(|a, b| b.departed_lines.cmp(&a.departed_lines)); // Note: Reverse sort
+
+    let mut directories: Vec<DirectoryLoss> = directories
+        .into_iter()
+        .filter(|&(_, ref loss)| loss.departed_lines > 0.0)
+        .map(|(path, loss)| DirectoryLoss { path: path, loss: loss })
+        .collect();
+    directories.sort_by(|a, b| {
+        b.loss.fraction_departed().partial_cmp(&a.loss.fraction_departed()).unwrap_or(
+            Ordering::Equal,
+        )
+    }); // Note: Reverse sort
+
+    let mut teams: Vec<(Option<&str>, LossShare)> = teams.into_iter().collect();
+    teams.sort_by(|a, b| {
+        b.1.departed_lines.partial_cmp(&a.1.departed_lines).unwrap_or(
+            Ordering::Equal,
+        )
+    }); // Note: Reverse sort
+
+    Ok(KnowledgeLossReport {
+        threshold: threshold,
+        repository: repository,
+        departed: departed,
+        orphaned_files: orphaned_files,
+        directories: directories,
+        teams: teams,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use git2::Oid;
+
+    use super::*;
+    use ownership::OwnedHunk;
+
+    /// 2024-03-01
+    const TODAY: i64 = 19_783;
+
+    fn person(name: &str, left: Option<&str>, teams: &[(&str, f64)]) -> Person {
+        let mut person = Person::new(name);
+        person.set_left(left.map(String::from));
+        for &(team, weight) in teams {
+            person.add_team(team, Some(weight));
+        }
+        person
+    }
+
+    fn file<'a>(path: &str, hunks: &[(&'a Person, u32)]) -> FileOwnership<'a> {
+        FileOwnership {
+            path: PathBuf::from(path),
+            hunks: hunks
+                .iter()
+                .map(|&(person, lines)| {
+                    OwnedHunk {
+                        person: person,
+                        lines: lines,
+                        start_line: 1,
+                        commit: Oid::zero(),
+                        day: 0,
+                        co_authors: Vec::new(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn names(people: &[&Person]) -> Vec<String> {
+        people.iter().map(|person| person.name().to_owned()).collect()
+    }
+
+    #[test]
+    fn it_finds_files_owned_by_departed_people() {
+        let alice = person("Alice", Some("2024-01-01"), &[("Backend", 3.0), ("Frontend", 1.0)]);
+        let bob = person("Bob", Some("2023-06-01"), &[("Frontend", 1.0)]);
+        let carol = person("Carol", None, &[("Backend", 1.0)]);
+        let dave = person("Dave", Some("2025-01-01"), &[]);
+
+        let files = vec![
+            file("src/a.rs", &[(&alice, 6), (&bob, 3), (&carol, 1)]),
+            file("src/b.rs", &[(&alice, 2), (&bob, 4), (&carol, 4)]),
+            // Exactly at the threshold, so not orphaned.
+            file("docs/c.md", &[(&alice, 5), (&carol, 5)]),
+            file("README.md", &[(&dave, 4)]),
+        ];
+
+        let report = calculate(&files, TODAY, 0.5, 1).unwrap();
+
+        assert_eq!(report.repository.total_lines, 34.0);
+        assert_eq!(report.repository.departed_lines, 20.0);
+
+        let departed: Vec<(&str, u32)> = report
+            .departed
+            .iter()
+            .map(|owner| (owner.person.name(), owner.lines))
+            .collect();
+        assert_eq!(departed, vec![("Alice", 13), ("Bob", 7)]);
+
+        let orphaned: Vec<(&str, u32, u32)> = report
+            .orphaned_files
+            .iter()
+            .map(|file| (file.path.as_str(), file.lines, file.departed_lines))
+            .collect();
+        assert_eq!(orphaned, vec![("src/a.rs", 10, 9), ("src/b.rs", 10, 6)]);
+        assert_eq!(names(&report.orphaned_files[0].former_owners), vec!["Alice", "Bob"]);
+        assert_eq!(names(&report.orphaned_files[1].former_owners), vec!["Bob", "Alice"]);
+
+        let directories: Vec<(&str, f64, f64)> = report
+            .directories
+            .iter()
+            .map(|directory| {
+                (directory.path.as_str(), directory.loss.total_lines, directory.loss.departed_lines)
+            })
+            .collect();
+        assert_eq!(directories, vec![("src", 20.0, 15.0), ("docs", 10.0, 5.0)]);
+
+        let teams: Vec<(Option<&str>, f64, f64)> = report
+            .teams
+            .iter()
+            .map(|&(name, ref loss)| (name, loss.total_lines, loss.departed_lines))
+            .collect();
+        assert_eq!(
+            teams,
+            vec![
+                (Some("Frontend"), 10.25, 10.25),
+                (Some("Backend"), 19.75, 9.75),
+                (None, 4.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_orphans_files_above_the_threshold_only() {
+        let alice = person("Alice", Some("2024-01-01"), &[]);
+        let carol = person("Carol", None, &[]);
+        let files = vec![file("src/a.rs", &[(&alice, 1), (&carol, 1)])];
+
+        assert!(calculate(&files, TODAY, 0.5, 1).unwrap().orphaned_files.is_empty());
+        assert_eq!(calculate(&files, TODAY, 0.49, 1).unwrap().orphaned_files.len(), 1);
+        // Nobody had left yet the day before.
+        assert!(calculate(&files, 19_722, 0.0, 1).unwrap().orphaned_files.is_empty());
+    }
+
+    #[test]
+    fn it_fails_on_unreadable_dates() {
+        let alice = person("Alice", Some("last spring"), &[]);
+        let files = vec![file("src/a.rs", &[(&alice, 1)])];
+
+        assert_eq!(
+            calculate(&files, TODAY, 0.5, 1).unwrap_err().to_string(),
+            "Invalid date: \"last spring\"\nPlease use the YYYY-MM-DD format."
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use git2::{Commit, Oid};
+
+use super::errors::*;
+use super::Context;
+use count;
+use dates;
+use ownership::FileOwnership;
+use person::{Person, CombinedTracking};
+use tree_walker::directory_at_depth;
+
+static PERCENTILES: [u32; 5] = [10, 25, 50, 75, 90];
+
+#[derive(Debug)]
+pub struct LineAgeStatistics<'context> {
+    pub today: i64,
+    pub repository: LineAges,
+    pub directories: BTreeMap<String, LineAges>,
+    pub combined_tracking: CombinedTracking<'context, LineAges>,
+    pub oldest_line: Option<OldestLine<'context>>,
+}
+
+/// The line in the codebase that has gone the longest without being changed.
+#[derive(Debug)]
+pub struct OldestLine<'context> {
+    pub path: String,
+    pub line_number: usize,
+    pub content: String,
+    pub commit: Oid,
+    pub day: i64,
+    pub person: &'context Person,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgePercentile {
+    pub percent: u32,
+    pub age_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedLineAges {
+    #[serde(serialize_with = "count::serialize")]
+    pub lines: f64,
+    pub median_age_days: Option<i64>,
+    pub percentiles: Vec<AgePercentile>,
+    /// Surviving lines by the year they were last changed in.
+    #[serde(serialize_with = "count::serialize_map")]
+    pub lines_per_year: BTreeMap<i64, f64>,
+}
+
+impl<'context> LineAgeStatistics<'context> {
+    pub fn repository_ages(&self) -> ComputedLineAges {
+        self.repository.compute(self.today)
+    }
+
+    pub fn directories(&self) -> Vec<(&str, ComputedLineAges)> {
+        self.directories
+            .iter()
+            .map(|(path, ages)| (path.as_str(), ages.compute(self.today)))
+            .collect()
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedLineAges)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, ages)| (*person, ages.compute(self.today)))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| b.1.median_age_days.cmp(&a.1.median_age_days));
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedLineAges)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, ages)| (team_name, ages.compute(self.today)))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| b.1.median_age_days.cmp(&a.1.median_age_days));
+        toplist
+    }
+}
+
+/// Surviving lines, grouped by the day they were last changed on. Teams only get their share of
+/// the lines of people that are in several teams, so their counts can be fractional.
+#[derive(Debug, Default)]
+pub struct LineAges {
+    lines_per_day: BTreeMap<i64, f64>,
+}
+
+impl LineAges {
+    fn add_lines(&mut self, day: i64, lines: f64) {
+        *self.lines_per_day.entry(day).or_insert(0.0) += lines;
+    }
+
+    fn total_lines(&self) -> f64 {
+        self.lines_per_day.values().fold(0.0, |total, lines| total + lines)
+    }
+
+    /// The age in days that `percent` percent of the lines are younger than or as old as.
+    fn percentile(&self, today: i64, percent: u32) -> Option<i64> {
+        let total_lines = self.total_lines();
+        if total_lines <= 0.0 {
+            return None;
+        }
+
+        let wanted = total_lines * f64::from(percent) / 100.0;
+        let mut seen = 0.0;
+        // Youngest lines first
+        for (day, lines) in self.lines_per_day.iter().rev() {
+            seen += *lines;
+            if seen >= wanted {
+                return Some((today - day).max(0));
+            }
+        }
+        // Only reached through rounding errors in fractional line counts; all lines were seen.
+        self.lines_per_day.keys().next().map(|day| (today - day).max(0))
+    }
+
+    pub fn compute(&self, today: i64) -> ComputedLineAges {
+        let mut lines_per_year = BTreeMap::new();
+        for (day, lines) in &self.lines_per_day {
+            let (year, _, _) = dates::civil_from_days(*day);
+            *lines_per_year.entry(year).or_insert(0.0) += *lines;
+        }
+
+        ComputedLineAges {
+            lines: self.total_lines(),
+            median_age_days: self.percentile(today, 50),
+            percentiles: PERCENTILES
+                .iter()
+                .filter_map(|percent| {
+                    self.percentile(today, *percent).map(|age_days| {
+                        AgePercentile {
+                            percent: *percent,
+                            age_days: age_days,
+                        }
+                    })
+                })
+                .collect(),
+            lines_per_year: lines_per_year,
+        }
+    }
+}
+
+/// Groups the age of every surviving line by directory (`depth` levels deep), person and team.
+pub fn calculate<'context>(
+    context: &'context Context,
+    commit: &Commit,
+    files: &[FileOwnership<'context>],
+    today: i64,
+    depth: usize,
+) -> Result<LineAgeStatistics<'context>> {
+    let mut repository = LineAges::default();
+    let mut directories: BTreeMap<String, LineAges> = BTreeMap::new();
+    let mut combined_tracking: CombinedTracking<LineAges> = CombinedTracking::new();
+    let mut oldest: Option<(&FileOwnership, usize)> = None;
+
+    for file in files {
+        let directory = directory_at_depth(&file.path, depth);
+
+        for (index, hunk) in file.hunks.iter().enumerate() {
+            let lines = f64::from(hunk.lines);
+            repository.add_lines(hunk.day, lines);
+            directories
+                .entry(directory.clone())
+                .or_insert_with(LineAges::default)
+                .add_lines(hunk.day, lines);
+            combined_tracking.track_person_weighted(
+                hunk.person,
+                |ages, weight| ages.add_lines(hunk.day, lines * weight),
+            );
+
+            let is_older = match oldest {
+                Some((oldest_file, oldest_index)) => hunk.day < oldest_file.hunks[oldest_index].day,
+                None => true,
+            };
+            if is_older {
+                oldest = Some((file, index));
+            }
+        }
+    }
+
+    let oldest_line = match oldest {
+        Some((file, index)) => Some(oldest_line(context, commit, file, index)?),
+        None => None,
+    };
+
+    Ok(LineAgeStatistics {
+        today: today,
+        repository: repository,
+        directories: directories,
+        combined_tracking: combined_tracking,
+        oldest_line: oldest_line,
+    })
+}
+
+fn oldest_line<'context>(
+    context: &'context Context,
+    commit: &Commit,
+    file: &FileOwnership<'context>,
+    index: usize,
+) -> Result<OldestLine<'context>> {
+    let hunk = &file.hunks[index];
+    let entry = commit.tree()?.get_path(&file.path)?;
+    let blob = context.repo().find_blob(entry.id())?;
+    let content = String::from_utf8_lossy(blob.content())
+        .lines()
+        .nth(hunk.start_line - 1)
+        .unwrap_or("")
+        .trim()
+        .to_owned();
+
+    Ok(OldestLine {
+        path: file.path.to_string_lossy().into_owned(),
+        line_number: hunk.start_line,
+        content: content,
+        commit: hunk.commit,
+        day: hunk.day,
+        person: hunk.person,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_computes_percentiles_from_the_youngest_lines() {
+        let mut ages = LineAges::default();
+        ages.add_lines(100, 2.0);
+        ages.add_lines(90, 5.0);
+        ages.add_lines(10, 3.0);
+
+        assert_eq!(ages.percentile(100, 10), Some(0));
+        assert_eq!(ages.percentile(100, 50), Some(10));
+        assert_eq!(ages.percentile(100, 75), Some(90));
+        assert_eq!(ages.percentile(100, 100), Some(90));
+        assert_eq!(LineAges::default().percentile(100, 50), None);
+    }
+
+    #[test]
+    fn it_groups_lines_by_year() {
+        let mut ages = LineAges::default();
+        ages.add_lines(dates::parse_day("2016-12-31").unwrap(), 4.0);
+        ages.add_lines(dates::parse_day("2017-01-01").unwrap(), 1.0);
+        ages.add_lines(dates::parse_day("2017-06-01").unwrap(), 2.0);
+
+        let computed = ages.compute(dates::parse_day("2018-01-01").unwrap());
+        assert_eq!(computed.lines, 7.0);
+        assert_eq!(computed.lines_per_year.get(&2016), Some(&4.0));
+        assert_eq!(computed.lines_per_year.get(&2017), Some(&3.0));
+    }
+}
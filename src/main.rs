@@ -35,9 +35,37 @@ mod person;
 use person::*;
 
 mod ownership;
+mod commits;
+mod activity;
+mod streaks;
+mod churn;
+mod hotspots;
+mod truck_factor;
+mod knowledge_loss;
+mod codeowners;
+mod codeowners_audit;
+mod line_age;
+mod collaboration;
+mod coupling;
+mod conventional;
+mod messages;
+mod commit_types;
+mod release_notes;
+mod newcomers;
+mod timeline;
+mod report;
+
+mod dates;
+mod count;
+mod coauthors;
+mod history;
+#[cfg(test)]
+mod test_repo;
+use history::{CommitWalker, Selection};
 
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::PathBuf;
 
 mod errors {
     error_chain! {
@@ -62,6 +90,18 @@ mod errors {
                 description("Config file not found")
                 display("Config file not found in {}.\nHint: Maybe you need to run the \"init\" command first?", path.display())
             }
+            InvalidDate(input: String) {
+                description("Invalid date")
+                display("Invalid date: \"{}\"\nPlease use the YYYY-MM-DD format.", input)
+            }
+            InvalidDateArgument(input: String) {
+                description("Invalid date")
+                display("Invalid date: \"{}\"\nPlease use the YYYY-MM-DD format or something like \"6 months ago\".", input)
+            }
+            InvalidRevision(revision: String) {
+                description("Invalid revision")
+                display("Invalid revision: \"{}\"", revision)
+            }
             UnknownEmail(email: super::Email) {
                 description("Unknown email")
                 display("Unknown email: \"{}\"\nPlease add it to a person in the configuration file.", email)
@@ -102,12 +142,14 @@ fn run() -> Result<()> {
                 .possible_values(formatters::POSSIBLE_VALUES)
                 .default_value("console")
                 .help(
-                    "Set the output format of this action."
+                    "Set the output format of this action. SVG charts can be drawn for ownership, \
+                     commits, activity, churn, line-age, commit-types, release-notes and timeline."
                 )
         )
         .subcommand(
             SubCommand::with_name("init")
                 .about("Initializes a new config for repository.")
+                .arg(revisions_arg())
                 .arg(Arg::with_name("dry_run").short("n").long("dry-run").visible_alias("stdout").help(
                     "Don't write generated config file to disk; instead output it on STDOUT.",
                 ))
@@ -118,6 +160,7 @@ fn run() -> Result<()> {
         .subcommand(
             SubCommand::with_name("update")
                 .about("Update config for repository")
+                .arg(revisions_arg())
                 .arg(Arg::with_name("dry_run").short("n").long("dry-run").visible_alias("stdout").help(
                     "Don't write generated config file to disk; instead output it on STDOUT.",
                 )),
@@ -125,6 +168,276 @@ fn run() -> Result<()> {
         .subcommand(
             SubCommand::with_name("ownership")
                 .about("Calculates line ownership")
+                .args(&selection_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("commits")
+                .about("Calculates commit activity")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("activity")
+                .about("Shows what times of the week commits are made")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("streaks")
+                .about("Finds streaks of consecutive days with commits")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("churn")
+                .about("Calculates lines added and removed")
+                .args(&selection_args())
+                .arg(revisions_arg())
+                .arg(Arg::with_name("include_merges").long("include-merges").help(
+                    "Also count changes made in merge commits, compared to their first parent.",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("hotspots")
+                .about("Finds big files that change often")
+                .args(&selection_args())
+                .arg(revisions_arg())
+                .arg(
+                    Arg::with_name("limit")
+                        .short("l")
+                        .long("limit")
+                        .takes_value(true)
+                        .default_value("20")
+                        .help("Number of files to show."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("truck-factor")
+                .visible_alias("bus-factor")
+                .about("Calculates how many people could leave before most files are orphaned")
+                .arg(
+                    Arg::with_name("owner_threshold")
+                        .long("owner-threshold")
+                        .takes_value(true)
+                        .default_value("0.25")
+                        .help("Share of a file someone must own to count as one of its owners."),
+                )
+                .arg(
+                    Arg::with_name("depth")
+                        .long("depth")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help("How many directory levels to report truck factors for."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("knowledge-loss")
+                .about("Finds code still owned by people that have left")
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .takes_value(true)
+                        .default_value("0.5")
+                        .help("Share of a file departed people must own for it to be orphaned."),
+                )
+                .arg(
+                    Arg::with_name("depth")
+                        .long("depth")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help("How many directory levels to group files by."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("line-age")
+                .about("Shows how old the surviving lines of code are")
+                .arg(
+                    Arg::with_name("depth")
+                        .long("depth")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help("How many directory levels to group files by."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("collaboration")
+                .about("Shows who works with whom")
+                .args(&selection_args())
+                .arg(revisions_arg())
+                .arg(Arg::with_name("dot").long("dot").help(
+                    "Output the graph in the Graphviz DOT language instead.",
+                ))
+                .arg(Arg::with_name("teams").short("t").long("teams").help(
+                    "Collapse the graph to the teams people are in.",
+                ))
+                .arg(
+                    Arg::with_name("window")
+                        .long("window")
+                        .takes_value(true)
+                        .default_value("30")
+                        .help("Days between changes to the same file for them to count as working together."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("coupling")
+                .about("Shows files that change together")
+                .args(&selection_args())
+                .arg(revisions_arg())
+                .arg(
+                    Arg::with_name("min_support")
+                        .long("min-support")
+                        .takes_value(true)
+                        .default_value("3")
+                        .help("Number of commits two files must share to be reported."),
+                )
+                .arg(
+                    Arg::with_name("min_confidence")
+                        .long("min-confidence")
+                        .takes_value(true)
+                        .default_value("0.5")
+                        .help("Share of the commits to a file that must also change the other one."),
+                )
+                .arg(
+                    Arg::with_name("max_commit_size")
+                        .long("max-commit-size")
+                        .takes_value(true)
+                        .default_value("30")
+                        .help("Skip commits changing more files than this."),
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .short("l")
+                        .long("limit")
+                        .takes_value(true)
+                        .default_value("20")
+                        .help("Number of file pairs to show."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("messages")
+                .about("Shows trivia about commit messages")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("commit-types")
+                .about("Shows Conventional Commits types per person and team")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("release-notes")
+                .about("Lists who contributed to a release")
+                .arg(Arg::with_name("from").required(true).help(
+                    "The previous release, like v1.0.",
+                ))
+                .arg(Arg::with_name("to").default_value("HEAD").help(
+                    "The release to list contributors of, like v2.0.",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("newcomers")
+                .about("Lists people who made their first commit recently")
+                .args(&selection_args())
+                .arg(Arg::with_name("ownership").long("ownership").help(
+                    "Also find the oldest lines each newcomer still owns. This runs blame on every file.",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("timeline")
+                .about("Shows how many commits were made each month")
+                .args(&selection_args())
+                .arg(revisions_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("report")
+                .about(
+                    "Writes ownership, commits, activity and a timeline into one HTML page, or into \
+                     JSON or YAML with --format",
+                )
+                .args(&selection_args())
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("Write the report to this file instead of STDOUT."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("codeowners")
+                .about("Generates a CODEOWNERS file from line ownership")
+                .arg(Arg::with_name("dry_run").short("n").long("dry-run").visible_alias("stdout").help(
+                    "Don't write generated CODEOWNERS file to disk; instead output it on STDOUT.",
+                ))
+                .arg(Arg::with_name("diff").short("d").long("diff").help(
+                    "Show how the generated file differs from the existing one instead of writing it.",
+                ))
+                .arg(Arg::with_name("teams").short("t").long("teams").help(
+                    "Assign directories to teams instead of people. Teams need a handle in the config.",
+                ))
+                .arg(
+                    Arg::with_name("style")
+                        .long("style")
+                        .takes_value(true)
+                        .possible_values(&["github", "gitlab"])
+                        .default_value("github")
+                        .help("Which code hosting service the file is for."),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .takes_value(true)
+                        .default_value("0.2")
+                        .help("Share of a directory someone must own to be listed as an owner."),
+                )
+                .arg(
+                    Arg::with_name("max_owners")
+                        .long("max-owners")
+                        .takes_value(true)
+                        .default_value("3")
+                        .help("Maximum number of owners per directory."),
+                )
+                .arg(
+                    Arg::with_name("depth")
+                        .long("depth")
+                        .takes_value(true)
+                        .default_value("2")
+                        .help("How many directory levels to generate rules for."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("audit-codeowners")
+                .about("Compares an existing CODEOWNERS file with line ownership")
+                .arg(
+                    Arg::with_name("style")
+                        .long("style")
+                        .takes_value(true)
+                        .possible_values(&["github", "gitlab"])
+                        .default_value("github")
+                        .help("Which code hosting service the file is for."),
+                )
+                .arg(
+                    Arg::with_name("file")
+                        .long("file")
+                        .takes_value(true)
+                        .help("Path to the CODEOWNERS file, if it is not in a standard location."),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .takes_value(true)
+                        .default_value("0.1")
+                        .help("Share of the matched lines listed owners must own for a rule to be fine."),
+                )
+                .arg(
+                    Arg::with_name("min_share")
+                        .long("min-share")
+                        .takes_value(true)
+                        .default_value("0.05")
+                        .help("Share of the repository someone must own to be expected in the file."),
+                ),
         );
     let matches = app.get_matches();
 
@@ -132,6 +445,24 @@ fn run() -> Result<()> {
         ("init", Some(args)) => init(args),
         ("update", Some(args)) => update(args),
         ("ownership", Some(args)) => ownership(args),
+        ("commits", Some(args)) => commits(args),
+        ("activity", Some(args)) => activity(args),
+        ("streaks", Some(args)) => streaks(args),
+        ("churn", Some(args)) => churn(args),
+        ("hotspots", Some(args)) => hotspots(args),
+        ("truck-factor", Some(args)) => truck_factor(args),
+        ("knowledge-loss", Some(args)) => knowledge_loss(args),
+        ("line-age", Some(args)) => line_age(args),
+        ("collaboration", Some(args)) => collaboration(args),
+        ("coupling", Some(args)) => coupling(args),
+        ("messages", Some(args)) => messages(args),
+        ("commit-types", Some(args)) => commit_types(args),
+        ("release-notes", Some(args)) => release_notes(args),
+        ("newcomers", Some(args)) => newcomers(args),
+        ("timeline", Some(args)) => timeline(args),
+        ("report", Some(args)) => report(args),
+        ("codeowners", Some(args)) => codeowners(args),
+        ("audit-codeowners", Some(args)) => codeowners_audit(args),
         // This should not happen considering SubcommandRequiredElseHelp setting above
         // It would happen if a new subcommand was added but not matched on here.
         _ => std::process::exit(1),
@@ -140,7 +471,8 @@ fn run() -> Result<()> {
 
 fn init(args: &ArgMatches) -> Result<()> {
     let repo = Repository::open_from_env()?;
-    let config_yaml_string = generate_initial_config(&repo)?;
+    let selection = selection_from_args(args)?;
+    let config_yaml_string = generate_initial_config(&repo, &selection)?;
     let config_file_path = config_file_path(&repo);
     let file_exists = config_file_path.exists();
 
@@ -173,13 +505,14 @@ fn init(args: &ArgMatches) -> Result<()> {
 
 fn update(args: &ArgMatches) -> Result<()> {
     let repo = Repository::open_from_env()?;
+    let selection = selection_from_args(args)?;
     let config = context::load_configuration(&repo)?;
-    if config.generated_at_sha == current_head_sha(&repo)? {
+    if config.generated_at_sha == selection_tip(&repo, &selection)?.to_string() {
         eprintln!("Config already up to date.");
         Ok(())
     } else {
         let config_file_path = config_file_path(&repo);
-        let new_config_yaml_string = update_config(&repo, config).chain_err(
+        let new_config_yaml_string = update_config(&repo, &selection, config).chain_err(
             || "Could not update config",
         )?;
         if args.is_present("dry_run") {
@@ -201,27 +534,343 @@ fn update(args: &ArgMatches) -> Result<()> {
 
 fn ownership(args: &ArgMatches) -> Result<()> {
     let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
 
     let context = Context::load()?;
     let head_commit = context.head_commit()?;
 
-    let owners = ownership::calculate(&context, &head_commit)?;
+    let owners = ownership::calculate(&context, &head_commit, &selection)?;
     format.display(&owners)
 }
 
-fn generate_initial_config(repo: &Repository) -> Result<String> {
-    let mut config_builder = ConfigurationBuilder::new();
-    let mut walker = repo.revwalk().unwrap();
+fn commits(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
+
+    let statistics = commits::calculate(&context, &selection)?;
+    format.display(&statistics)
+}
+
+fn activity(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
+
+    let statistics = activity::calculate(&context, &selection)?;
+    format.display(&statistics)
+}
+
+fn streaks(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
+
+    let statistics = streaks::calculate(&context, &selection)?;
+    format.display(&statistics)
+}
+
+fn churn(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
+
+    let statistics = churn::calculate(&context, &selection, args.is_present("include_merges"))?;
+    format.display(&statistics)
+}
+
+fn hotspots(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+    let limit = value_t!(args, "limit", usize).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+
+    let statistics = hotspots::calculate(&context, &selection, limit)?;
+    format.display(&statistics)
+}
+
+fn truck_factor(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let owner_threshold = value_t!(args, "owner_threshold", f64).unwrap_or_else(|e| e.exit());
+    let depth = value_t!(args, "depth", usize).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+    let head_commit = context.head_commit()?;
+
+    let files = ownership::blame_files(&context, &head_commit)?;
+    let report = truck_factor::calculate(&files, owner_threshold, depth);
+    format.display(&report)
+}
+
+fn knowledge_loss(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let threshold = value_t!(args, "threshold", f64).unwrap_or_else(|e| e.exit());
+    let depth = value_t!(args, "depth", usize).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+    let head_commit = context.head_commit()?;
+
+    let files = ownership::blame_files(&context, &head_commit)?;
+    let report = knowledge_loss::calculate(&files, dates::today(), threshold, depth)?;
+    format.display(&report)
+}
+
+fn line_age(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let depth = value_t!(args, "depth", usize).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+    let head_commit = context.head_commit()?;
+
+    let files = ownership::blame_files(&context, &head_commit)?;
+    let statistics = line_age::calculate(&context, &head_commit, &files, dates::today(), depth)?;
+    format.display(&statistics)
+}
+
+fn collaboration(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+    let window = value_t!(args, "window", i64).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+
+    let graph = collaboration::calculate(&context, &selection, window, args.is_present("teams"))?;
+    if args.is_present("dot") {
+        print!("{}", graph.to_dot());
+        Ok(())
+    } else {
+        format.display(&graph)
+    }
+}
+
+fn coupling(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+    let options = coupling::Options {
+        min_support: value_t!(args, "min_support", u32).unwrap_or_else(|e| e.exit()),
+        min_confidence: value_t!(args, "min_confidence", f64).unwrap_or_else(|e| e.exit()),
+        max_commit_size: value_t!(args, "max_commit_size", usize).unwrap_or_else(|e| e.exit()),
+        limit: value_t!(args, "limit", usize).unwrap_or_else(|e| e.exit()),
+    };
+
+    let context = Context::load()?;
+
+    let statistics = coupling::calculate(&context, &selection, &options)?;
+    format.display(&statistics)
+}
+
+fn messages(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+    let context = Context::load()?;
+    let statistics = messages::calculate(&context, &selection)?;
+    format.display(&statistics)
+}
+
+fn commit_types(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+    let context = Context::load()?;
+    let statistics = commit_types::calculate(&context, &selection)?;
+    format.display(&statistics)
+}
+
+fn release_notes(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let from = args.value_of("from").unwrap();
+    let to = args.value_of("to").unwrap();
+
+    let context = Context::load()?;
+
+    let notes = release_notes::calculate(&context, from, to)?;
+    format.display(&notes)
+}
+
+fn newcomers(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let mut selection = selection_from_args(args)?;
+    if selection.since.is_none() && selection.until.is_none() {
+        selection.since = Some(dates::today() - 90);
+    }
+
+    let context = Context::load()?;
+
+    let mut report = newcomers::calculate(&context, &selection)?;
+    if args.is_present("ownership") {
+        let files = ownership::blame_files(&context, &context.head_commit()?)?;
+        newcomers::add_ownership(&mut report, &files);
+    }
+    format.display(&report)
+}
+
+fn timeline(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
 
-    config_builder.set_latest_commit_sha(current_head_sha(repo)?);
+    let timeline = timeline::calculate(&context, &selection)?;
+    format.display(&timeline)
+}
+
+fn report(args: &ArgMatches) -> Result<()> {
+    // Reports are meant for browsers, so they are HTML unless another format is asked for.
+    let format = if args.occurrences_of("format") == 0 {
+        formatters::Format::HTML
+    } else {
+        formatters::from_args(args)?
+    };
+    let selection = selection_from_args(args)?;
+
+    let context = Context::load()?;
+
+    let report = report::calculate(&context, &selection)?;
+    match args.value_of("output") {
+        Some(path) => {
+            let mut file = File::create(path)?;
+            format.write_report(&report, &mut file)?;
+            eprintln!("Report written to {}", path);
+            Ok(())
+        }
+        None => format.write_report(&report, &mut ::std::io::stdout()),
+    }
+}
+
+fn codeowners(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let options = codeowners::Options {
+        style: codeowners::Style::from_name(args.value_of("style").unwrap_or("github"))?,
+        threshold: value_t!(args, "threshold", f64).unwrap_or_else(|e| e.exit()),
+        max_owners: value_t!(args, "max_owners", usize).unwrap_or_else(|e| e.exit()),
+        depth: value_t!(args, "depth", usize).unwrap_or_else(|e| e.exit()),
+        teams: args.is_present("teams"),
+    };
+
+    let context = Context::load()?;
+    let head_commit = context.head_commit()?;
 
-    walker.push_head()?;
-    let commits = walker.flat_map(std::result::Result::ok).flat_map(|oid| {
-        repo.find_commit(oid)
-    });
+    let files = ownership::blame_files(&context, &head_commit)?;
+    let generated = codeowners::generate(&context, &files, &options);
+    for team_name in &generated.skipped_teams {
+        eprintln!("Skipping team {}, which has no handle configured", team_name);
+    }
+    let file_path = codeowners::file_path(&context, options.style)?;
 
-    for commit in commits {
-        config_builder.add_author(commit.author());
+    if args.is_present("diff") {
+        let mut existing = String::new();
+        if file_path.exists() {
+            File::open(&file_path)?.read_to_string(&mut existing)?;
+        }
+        eprintln!("Comparing with {}", file_path.display());
+        for line in codeowners::diff_lines(&existing, &generated.to_file_contents()) {
+            println!("{}", line);
+        }
+        Ok(())
+    } else if args.is_present("dry_run") {
+        eprintln!("Would write to this file: {}", file_path.display());
+        format.display(&generated)
+    } else {
+        if let Some(directory) = file_path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        let mut file = File::create(&file_path)?;
+        file.write_all(generated.to_file_contents().as_bytes())?;
+        eprintln!("CODEOWNERS written to {}", file_path.display());
+        Ok(())
+    }
+}
+
+fn codeowners_audit(args: &ArgMatches) -> Result<()> {
+    let format = formatters::from_args(args)?;
+    let style = codeowners::Style::from_name(args.value_of("style").unwrap_or("github"))?;
+    let threshold = value_t!(args, "threshold", f64).unwrap_or_else(|e| e.exit());
+    let min_share = value_t!(args, "min_share", f64).unwrap_or_else(|e| e.exit());
+
+    let context = Context::load()?;
+    let head_commit = context.head_commit()?;
+
+    let file_path = match args.value_of("file") {
+        Some(path) => PathBuf::from(path),
+        None => codeowners::file_path(&context, style)?,
+    };
+    if !file_path.exists() {
+        bail!("No CODEOWNERS file found at {}", file_path.display());
+    }
+    let mut contents = String::new();
+    File::open(&file_path)?.read_to_string(&mut contents)?;
+
+    let files = ownership::blame_files(&context, &head_commit)?;
+    let people: Vec<&Person> = context.people_db().iter().collect();
+    let audit = codeowners_audit::calculate(
+        file_path.to_string_lossy().into_owned(),
+        codeowners::parse(&contents),
+        &files,
+        &people,
+        context.teams(),
+        threshold,
+        min_share,
+    );
+    format.display(&audit)
+}
+
+fn selection_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("since")
+            .long("since")
+            .visible_alias("after")
+            .takes_value(true)
+            .help("Only look at commits made on or after this date, like 2017-06-01 or \"6 months ago\"."),
+        Arg::with_name("until")
+            .long("until")
+            .visible_alias("before")
+            .takes_value(true)
+            .help("Only look at commits made on or before this date."),
+    ]
+}
+
+fn revisions_arg() -> Arg<'static, 'static> {
+    Arg::with_name("revisions")
+        .multiple(true)
+        .help(
+            "Only look at these revisions, like v1.0..v2.0, main...feature or main ^v1.0. Defaults to HEAD.",
+        )
+}
+
+fn selection_from_args(args: &ArgMatches) -> Result<Selection> {
+    let today = dates::today();
+    let parse = |name| -> Result<Option<i64>> {
+        match args.value_of(name) {
+            Some(input) => {
+                match dates::parse_relative_day(input, today) {
+                    Some(day) => Ok(Some(day)),
+                    None => bail!(ErrorKind::InvalidDateArgument(input.to_owned())),
+                }
+            }
+            None => Ok(None),
+        }
+    };
+
+    Ok(Selection {
+        since: parse("since")?,
+        until: parse("until")?,
+        revisions: args.values_of("revisions")
+            .map(|revisions| revisions.map(String::from).collect())
+            .unwrap_or_default(),
+    })
+}
+
+fn generate_initial_config(repo: &Repository, selection: &Selection) -> Result<String> {
+    let mut config_builder = ConfigurationBuilder::new();
+
+    config_builder.set_latest_commit_sha(selection_tip(repo, selection)?.to_string());
+
+    for commit in CommitWalker::new(repo, selection)? {
+        add_contributors(&mut config_builder, &commit);
     }
 
     let configuration = config_builder.into_configuration()?;
@@ -229,27 +878,27 @@ fn generate_initial_config(repo: &Repository) -> Result<String> {
     Ok(serde_yaml::to_string(&configuration)?)
 }
 
-fn update_config(repo: &Repository, configuration: Configuration) -> Result<String> {
+fn update_config(
+    repo: &Repository,
+    selection: &Selection,
+    configuration: Configuration,
+) -> Result<String> {
     let old_head = configuration.generated_at_sha.clone();
 
     let mut config_builder = ConfigurationBuilder::from_existing(configuration);
-    let mut walker = repo.revwalk().unwrap();
+    let mut walker = CommitWalker::new(repo, selection)?;
 
-    config_builder.set_latest_commit_sha(current_head_sha(repo)?);
+    config_builder.set_latest_commit_sha(selection_tip(repo, selection)?.to_string());
+    config_builder.set_joined_date(dates::format_day(dates::today()));
 
-    walker.push_head()?;
     let old_head_oid = Oid::from_str(&old_head).chain_err(
         || "Could not parse generated_at_sha configuration SHA",
     )?;
 
     walker.hide(old_head_oid)?;
 
-    let commits = walker.flat_map(std::result::Result::ok).flat_map(|oid| {
-        repo.find_commit(oid)
-    });
-
-    for commit in commits {
-        config_builder.add_author(commit.author());
+    for commit in walker {
+        add_contributors(&mut config_builder, &commit);
     }
 
     let configuration = config_builder.into_configuration()?;
@@ -257,6 +906,23 @@ fn update_config(repo: &Repository, configuration: Configuration) -> Result<Stri
     Ok(serde_yaml::to_string(&configuration)?)
 }
 
-fn current_head_sha(repo: &Repository) -> Result<String> {
-    Ok(repo.head()?.resolve()?.target().unwrap().to_string())
+fn add_contributors(config_builder: &mut ConfigurationBuilder, commit: &git2::Commit) {
+    config_builder.add_author(commit.author());
+    for co_author in coauthors::parse_trailers(commit.message().unwrap_or("")) {
+        config_builder.add_identity(&co_author.name, &co_author.email);
+    }
+}
+
+/// The commit recorded in the config as the one it was generated at, so that `update` knows
+/// where to continue from.
+fn selection_tip(repo: &Repository, selection: &Selection) -> Result<Oid> {
+    match history::tip(repo, selection)? {
+        Some(tip) => Ok(tip),
+        None => {
+            bail!(
+                "The config can only be generated from revisions ending at a single commit, \
+                 like v1.0..v2.0"
+            )
+        }
+    }
 }
@@ -0,0 +1,309 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use git2::Commit;
+
+use super::errors::*;
+use super::Context;
+use count;
+use conventional;
+use history::{self, CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+/// Words too common to say anything about someone.
+static STOP_WORDS: [&'static str; 16] = [
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "into", "is", "of", "on", "the", "to",
+    "with",
+];
+
+#[derive(Debug)]
+pub struct MessageStatistics<'context> {
+    pub repository: MessageScore,
+    pub longest_subjects: Vec<Subject<'context>>,
+    pub shortest_subjects: Vec<Subject<'context>>,
+    pub combined_tracking: CombinedTracking<'context, MessageScore>,
+}
+
+#[derive(Debug)]
+pub struct Subject<'context> {
+    pub sha: String,
+    pub person: &'context Person,
+    pub subject: String,
+}
+
+impl<'context> Subject<'context> {
+    pub fn length(&self) -> usize {
+        self.subject.chars().count()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedMessages {
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub conventional_commits: f64,
+    pub conventional_share: f64,
+    pub average_subject_length: f64,
+    #[serde(serialize_with = "count::serialize")]
+    pub emoji: f64,
+    pub favorite_emoji: Option<String>,
+    pub most_used_word: Option<String>,
+    /// The most common first words of subjects, most common first.
+    pub first_words: Vec<WordCount>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct WordCount {
+    pub word: String,
+    #[serde(serialize_with = "count::serialize")]
+    pub count: f64,
+}
+
+impl<'context> MessageStatistics<'context> {
+    pub fn repository_messages(&self) -> ComputedMessages {
+        self.repository.compute()
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedMessages)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, score)| (*person, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedMessages)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, score)| (team_name, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+}
+
+/// Message trivia of a person or team. Teams only get their share of the messages of people that
+/// are in several teams, so their counts can be fractional.
+#[derive(Debug, Default)]
+pub struct MessageScore {
+    commits: f64,
+    conventional_commits: f64,
+    subject_characters: f64,
+    emoji: HashMap<String, f64>,
+    words: HashMap<String, f64>,
+    first_words: HashMap<String, f64>,
+}
+
+impl MessageScore {
+    fn add_message(&mut self, subject: &str, message: &str, share: f64) {
+        self.commits += share;
+        self.subject_characters += subject.chars().count() as f64 * share;
+        if conventional::parse(subject).is_some() {
+            self.conventional_commits += share;
+        }
+
+        for emoji in find_emoji(message) {
+            *self.emoji.entry(emoji).or_insert(0.0) += share;
+        }
+
+        let words = words(subject);
+        if let Some(first_word) = words.first() {
+            *self.first_words.entry(first_word.clone()).or_insert(0.0) += share;
+        }
+        for word in words {
+            if !STOP_WORDS.contains(&word.as_str()) {
+                *self.words.entry(word).or_insert(0.0) += share;
+            }
+        }
+    }
+
+    fn compute(&self) -> ComputedMessages {
+        let first_words = toplist(&self.first_words)
+            .into_iter()
+            .take(10)
+            .map(|(word, count)| {
+                WordCount {
+                    word: word,
+                    count: count,
+                }
+            })
+            .collect();
+
+        ComputedMessages {
+            commits: self.commits,
+            conventional_commits: self.conventional_commits,
+            conventional_share: if self.commits > 0.0 {
+                self.conventional_commits / self.commits
+            } else {
+                0.0
+            },
+            average_subject_length: if self.commits > 0.0 {
+                self.subject_characters / self.commits
+            } else {
+                0.0
+            },
+            emoji: self.emoji.values().fold(0.0, |total, count| total + count),
+            favorite_emoji: toplist(&self.emoji).into_iter().next().map(|(emoji, _)| emoji),
+            most_used_word: toplist(&self.words).into_iter().next().map(|(word, _)| word),
+            first_words: first_words,
+        }
+    }
+}
+
+/// Counts sorted with the highest first, and alphabetically when tied.
+fn toplist(counts: &HashMap<String, f64>) -> Vec<(String, f64)> {
+    let mut toplist: Vec<(String, f64)> = counts
+        .iter()
+        .map(|(key, count)| (key.clone(), *count))
+        .collect();
+    // Note: Reverse sort
+    toplist.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal).then_with(|| a.0.cmp(&b.0))
+    });
+    toplist
+}
+
+/// The lowercased words of a text, without punctuation.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-')
+        .map(|word| word.trim_matches(|c| c == '\'' || c == '-'))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Finds emoji in a message, both as Unicode characters and as `:shortcodes:` like gitmoji uses.
+fn find_emoji(message: &str) -> Vec<String> {
+    let mut found: Vec<String> = message
+        .chars()
+        .filter(|c| is_emoji(*c))
+        .map(|c| c.to_string())
+        .collect();
+
+    let mut rest = message;
+    while let Some(start) = rest.find(':') {
+        let after_start = &rest[start + 1..];
+        match after_start.find(':') {
+            Some(end) => {
+                let name = &after_start[0..end];
+                if is_shortcode(name) {
+                    found.push(format!(":{}:", name));
+                    rest = &after_start[end + 1..];
+                } else {
+                    rest = &after_start[end..];
+                }
+            }
+            None => break,
+        }
+    }
+
+    found
+}
+
+fn is_shortcode(name: &str) -> bool {
+    !name.is_empty() && !name.chars().all(|c| c.is_ascii_digit()) &&
+        name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '+' || c == '-'
+        })
+}
+
+fn is_emoji(c: char) -> bool {
+    let code = c as u32;
+    (code >= 0x1F300 && code <= 0x1FAFF) || (code >= 0x2600 && code <= 0x27BF) ||
+        code == 0x2B50 || code == 0x2B55
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<MessageStatistics<'context>> {
+    let people_db = context.people_db();
+
+    let mut repository = MessageScore::default();
+    let mut tracking: CombinedTracking<MessageScore> = CombinedTracking::new();
+    let mut subjects = Vec::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        // Merge commits mostly have generated messages.
+        if history::is_merge(&commit) {
+            continue;
+        }
+
+        let person = people_db.find_by_signature(commit.author())?;
+        let subject = subject(&commit);
+        let message = commit.message().unwrap_or("");
+
+        repository.add_message(&subject, message, 1.0);
+        tracking.track_person_weighted(
+            person,
+            |score, weight| score.add_message(&subject, message, weight),
+        );
+
+        if !subject.is_empty() {
+            subjects.push(Subject {
+                sha: commit.id().to_string(),
+                person: person,
+                subject: subject,
+            });
+        }
+    }
+
+    subjects.sort_by(|a, b| b.length().cmp(&a.length())); // Note: Reverse sort
+    // Subjects are listed as either long or short, never both.
+    let shortest_start = subjects.len().saturating_sub(5).max(subjects.len().min(5));
+    let mut shortest_subjects = subjects.split_off(shortest_start);
+    shortest_subjects.reverse();
+    subjects.truncate(5);
+
+    Ok(MessageStatistics {
+        repository: repository,
+        longest_subjects: subjects,
+        shortest_subjects: shortest_subjects,
+        combined_tracking: tracking,
+    })
+}
+
+fn subject(commit: &Commit) -> String {
+    commit.summary().unwrap_or("").trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_emoji_and_shortcodes() {
+        assert_eq!(
+            find_emoji("🐛 Fix crash :bug: at 10:30:00 :+1:"),
+            vec!["🐛", ":bug:", ":+1:"]
+        );
+        assert!(find_emoji("fix: Plain message").is_empty());
+    }
+
+    #[test]
+    fn it_counts_words_and_first_words() {
+        let mut score = MessageScore::default();
+        score.add_message("Fix the parser", "", 1.0);
+        score.add_message("fix: Parser crash", "", 1.0);
+        score.add_message("Add tests for the parser", "", 1.0);
+
+        let computed = score.compute();
+        assert_eq!(computed.commits, 3.0);
+        assert_eq!(computed.conventional_commits, 1.0);
+        assert_eq!(computed.most_used_word, Some(String::from("parser")));
+        let first_words: Vec<(&str, f64)> = computed
+            .first_words
+            .iter()
+            .map(|word| (word.word.as_str(), word.count))
+            .collect();
+        assert_eq!(first_words, vec![("fix", 2.0), ("add", 1.0)]);
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use git2::{Commit, Oid};
+
+use super::errors::*;
+use super::Context;
+use coauthors;
+use dates;
+use history::{self, CommitWalker, Selection};
+use ownership::FileOwnership;
+use person::Person;
+
+#[derive(Debug)]
+pub struct NewcomerReport<'context> {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// Newcomers in the order they made their first commit.
+    pub newcomers: Vec<Newcomer<'context>>,
+}
+
+#[derive(Debug)]
+pub struct Newcomer<'context> {
+    pub person: &'context Person,
+    pub first_commit: FirstCommit,
+    /// The day `update` added this person to the config, if it has been recorded.
+    pub joined: Option<i64>,
+    /// All commits by this person so far, including the first one.
+    pub commits: u32,
+    /// The oldest lines this person still owns, when ownership was looked at.
+    pub first_owned: Option<FirstOwnedLines>,
+}
+
+#[derive(Debug)]
+pub struct FirstCommit {
+    pub sha: String,
+    pub day: i64,
+    pub subject: String,
+    /// The files the commit changed.
+    pub files: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct FirstOwnedLines {
+    pub path: String,
+    pub day: i64,
+    pub lines: u32,
+}
+
+#[derive(Debug)]
+struct History {
+    first_commit: Oid,
+    first_day: i64,
+    commits: u32,
+}
+
+/// Everyone that is credited for a commit, co-authors included; pairing on a first commit is a
+/// first contribution too.
+fn contributors<'context>(
+    context: &'context Context,
+    commit: &Commit,
+) -> Result<Vec<&'context Person>> {
+    let people_db = context.people_db();
+    let mut contributors = vec![people_db.find_by_signature(commit.author())?];
+    for co_author in coauthors::co_authors(people_db, commit) {
+        if !contributors.contains(&co_author) {
+            contributors.push(co_author);
+        }
+    }
+    Ok(contributors)
+}
+
+/// Finds the people whose first commit was made within the dates of the selection.
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<NewcomerReport<'context>> {
+    let repo = context.repo();
+
+    let mut histories: HashMap<&Person, History> = HashMap::new();
+    for commit in CommitWalker::new(repo, &selection.without_dates())? {
+        let day = dates::local_day(&commit.author().when());
+        for person in contributors(context, &commit)? {
+            let history = histories.entry(person).or_insert_with(|| {
+                History {
+                    first_commit: commit.id(),
+                    first_day: day,
+                    commits: 0,
+                }
+            });
+            history.commits += 1;
+            // The walk is not strictly ordered by date when branches were merged.
+            if day <= history.first_day {
+                history.first_commit = commit.id();
+                history.first_day = day;
+            }
+        }
+    }
+
+    let mut newcomers = Vec::new();
+    for (person, history) in histories {
+        if !selection.includes_day(history.first_day) {
+            continue;
+        }
+
+        let commit = repo.find_commit(history.first_commit)?;
+        let files = history::changed_files(repo, &commit)?
+            .into_iter()
+            .map(|change| change.path.to_string_lossy().into_owned())
+            .collect();
+
+        newcomers.push(Newcomer {
+            person: person,
+            first_commit: FirstCommit {
+                sha: commit.id().to_string(),
+                day: history.first_day,
+                subject: commit.summary().unwrap_or("").to_owned(),
+                files: files,
+            },
+            joined: person.joined_day()?,
+            commits: history.commits,
+            first_owned: None,
+        });
+    }
+    newcomers.sort_by(|a, b| {
+        a.first_commit
+            .day
+            .cmp(&b.first_commit.day)
+            .then_with(|| a.person.name().cmp(b.person.name()))
+    });
+
+    Ok(NewcomerReport {
+        since: selection.since,
+        until: selection.until,
+        newcomers: newcomers,
+    })
+}
+
+/// Adds the oldest lines each newcomer still owns, from the blame of the current files.
+pub fn add_ownership(report: &mut NewcomerReport, files: &[FileOwnership]) {
+    for newcomer in &mut report.newcomers {
+        newcomer.first_owned = first_owned_lines(newcomer.person, files);
+    }
+}
+
+fn first_owned_lines(person: &Person, files: &[FileOwnership]) -> Option<FirstOwnedLines> {
+    let mut first: Option<FirstOwnedLines> = None;
+    for file in files {
+        for hunk in &file.hunks {
+            if hunk.person != person && !hunk.co_authors.contains(&person) {
+                continue;
+            }
+            let is_older = first.as_ref().map_or(true, |first| hunk.day < first.day);
+            if is_older {
+                first = Some(FirstOwnedLines {
+                    path: file.path.to_string_lossy().into_owned(),
+                    day: hunk.day,
+                    lines: hunk.lines,
+                });
+            }
+        }
+    }
+    first
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use ownership::OwnedHunk;
+
+    fn hunk<'a>(person: &'a Person, day: i64, lines: u32) -> OwnedHunk<'a> {
+        OwnedHunk {
+            person: person,
+            lines: lines,
+            start_line: 1,
+            commit: Oid::zero(),
+            day: day,
+            co_authors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn it_finds_the_oldest_lines_someone_owns() {
+        let alice = Person::new("Alice");
+        let bob = Person::new("Bob");
+
+        let files = vec![
+            FileOwnership {
+                path: PathBuf::from("src/lib.rs"),
+                hunks: vec![hunk(&alice, 10, 5), hunk(&bob, 3, 2)],
+            },
+            FileOwnership {
+                path: PathBuf::from("README.md"),
+                hunks: vec![hunk(&alice, 7, 1), hunk(&alice, 12, 4)],
+            },
+        ];
+
+        let first = first_owned_lines(&alice, &files).unwrap();
+        assert_eq!(first.path, "README.md");
+        assert_eq!(first.day, 7);
+        assert_eq!(first.lines, 1);
+
+        assert!(first_owned_lines(&Person::new("Carol"), &files).is_none());
+    }
+}
@@ -1,22 +1,33 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 use indicatif::{ProgressBar, ProgressStyle};
-use git2::{Commit, BlameOptions, BlameHunk};
+use git2::{Commit, BlameOptions, Oid};
 
 use super::errors::*;
 use super::{TreeWalker, Context};
+use coauthors::{self, CreditMode};
+use count;
+use dates;
+use history::Selection;
 use person::{Person, CombinedTracking};
 
 #[derive(Debug)]
 pub struct OwnershipStatistics<'context> {
     pub total_lines: u32,
+    pub credit_mode: CreditMode,
     pub combined_tracking: CombinedTracking<'context, OwnershipScore>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ComputedOwnership {
-    pub total_lines_owned: u32,
+    #[serde(serialize_with = "count::serialize")]
+    pub total_lines_owned: f64,
     pub fraction_owned: f32,
+    /// True when only part of some lines was credited, because they were split between teams or
+    /// shared with co-authors.
+    pub weighted: bool,
 }
 
 impl ComputedOwnership {
@@ -41,20 +52,19 @@ impl PartialOrd for ComputedOwnership {
 
 impl Ord for ComputedOwnership {
     fn cmp(&self, other: &ComputedOwnership) -> Ordering {
-        self.total_lines_owned.cmp(&other.total_lines_owned)
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
     }
 }
 
 impl<'context> OwnershipStatistics<'context> {
     pub fn from_tracking(
+        total_lines: u32,
+        credit_mode: CreditMode,
         owners: CombinedTracking<'context, OwnershipScore>,
     ) -> OwnershipStatistics<'context> {
-        let total_lines = owners
-            .people_iter()
-            .map(|(_, score)| score.total_lines_owned)
-            .sum();
         OwnershipStatistics {
             total_lines: total_lines,
+            credit_mode: credit_mode,
             combined_tracking: owners,
         }
     }
@@ -63,6 +73,31 @@ impl<'context> OwnershipStatistics<'context> {
         self.total_lines
     }
 
+    /// Explains why some of the lines owned are weighted, and when the percentages cannot be
+    /// expected to add up to 100%.
+    pub fn notes(&self) -> Vec<&'static str> {
+        let scores: Vec<&OwnershipScore> = self.combined_tracking
+            .people_iter()
+            .map(|(_, score)| score)
+            .chain(self.combined_tracking.team_iter().map(|(_, score)| score))
+            .collect();
+
+        let mut notes = Vec::new();
+        if scores.iter().any(|score| score.split_between_teams) {
+            notes.push("* Lines of people in several teams are split between their teams.");
+        }
+        if scores.iter().any(|score| score.shared_with_co_authors) {
+            notes.push("* Lines of co-authored commits are shared with the co-authors.");
+        }
+        if self.credit_mode == CreditMode::Duplicate {
+            notes.push(
+                "Co-authors get full credit for the lines of their commits, so percentages can \
+                 add up to more than 100%.",
+            );
+        }
+        notes
+    }
+
     pub fn people_toplist(&self) -> Vec<(&Person, ComputedOwnership)> {
         let mut toplist: Vec<_> = self.combined_tracking
             .people_iter()
@@ -87,35 +122,140 @@ impl<'context> OwnershipStatistics<'context> {
         ComputedOwnership {
             total_lines_owned: score.total_lines_owned,
             fraction_owned: (score.total_lines_owned as f32 / self.total_lines as f32),
+            weighted: score.split_between_teams || score.shared_with_co_authors,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct OwnershipScore {
-    pub total_lines_owned: u32,
+    pub total_lines_owned: f64,
+    pub split_between_teams: bool,
+    pub shared_with_co_authors: bool,
 }
 
 impl Default for OwnershipScore {
     fn default() -> OwnershipScore {
-        OwnershipScore { total_lines_owned: 0 }
+        OwnershipScore {
+            total_lines_owned: 0.0,
+            split_between_teams: false,
+            shared_with_co_authors: false,
+        }
     }
 }
 
 impl OwnershipScore {
-    fn add_hunk(&mut self, hunk: &BlameHunk) {
-        self.total_lines_owned += hunk.lines_in_hunk() as u32;
+    /// Credits `team_weight` of the lines for membership in a team, times the `share` of a
+    /// co-authored commit.
+    fn add_lines(&mut self, lines: u32, team_weight: f64, share: f64) {
+        self.total_lines_owned += lines as f64 * team_weight * share;
+        if team_weight < 1.0 {
+            self.split_between_teams = true;
+        }
+        if share < 1.0 {
+            self.shared_with_co_authors = true;
+        }
+    }
+}
+
+/// A run of lines in a file that blame attributes to a single person.
+#[derive(Debug)]
+pub struct OwnedHunk<'context> {
+    pub person: &'context Person,
+    pub lines: u32,
+    /// The first line of the hunk in the blamed version of the file, starting at 1.
+    pub start_line: usize,
+    /// The commit that last changed these lines, and the day it was made on.
+    pub commit: Oid,
+    pub day: i64,
+    /// Known co-authors of that commit. Only looked up when co-authors are credited.
+    pub co_authors: Vec<&'context Person>,
+}
+
+/// The blame of a single file.
+#[derive(Debug)]
+pub struct FileOwnership<'context> {
+    pub path: PathBuf,
+    pub hunks: Vec<OwnedHunk<'context>>,
+}
+
+impl<'context> FileOwnership<'context> {
+    pub fn total_lines(&self) -> u32 {
+        self.hunks.iter().map(|hunk| hunk.lines).sum()
+    }
+
+    pub fn lines_per_person(&self) -> HashMap<&'context Person, u32> {
+        let mut lines = HashMap::new();
+        for hunk in &self.hunks {
+            *lines.entry(hunk.person).or_insert(0) += hunk.lines;
+        }
+        lines
+    }
+
+    /// Lines per team, where people in several teams have their lines split over them according to
+    /// the weights of their memberships. People without a team are counted under `None`.
+    pub fn lines_per_team(&self) -> HashMap<Option<&'context str>, f64> {
+        let mut lines = HashMap::new();
+        for hunk in &self.hunks {
+            let memberships = hunk.person.team_memberships();
+            if memberships.is_empty() {
+                *lines.entry(None).or_insert(0.0) += hunk.lines as f64;
+            }
+            for (team_name, weight) in memberships {
+                *lines.entry(Some(team_name)).or_insert(0.0) += hunk.lines as f64 * weight;
+            }
+        }
+        lines
     }
 }
 
+/// Calculates who owns the lines in the tree of the given commit. Only lines last changed within
+/// the selection are counted.
 pub fn calculate<'context>(
     context: &'context Context,
     commit: &Commit,
+    selection: &Selection,
 ) -> Result<OwnershipStatistics<'context>> {
+    let mut files = blame_files(context, commit)?;
+    for file in &mut files {
+        file.hunks.retain(|hunk| selection.includes_day(hunk.day));
+    }
+    Ok(from_files(&files, context.credit_mode()))
+}
+
+pub fn from_files<'context>(
+    files: &[FileOwnership<'context>],
+    credit_mode: CreditMode,
+) -> OwnershipStatistics<'context> {
+    let mut owners: CombinedTracking<OwnershipScore> = CombinedTracking::new();
+    let mut total_lines = 0;
+
+    for file in files {
+        total_lines += file.total_lines();
+        for hunk in &file.hunks {
+            for (person, share) in coauthors::credit(hunk.person, &hunk.co_authors, credit_mode) {
+                owners.track_person_weighted(
+                    person,
+                    |score, weight| score.add_lines(hunk.lines, weight, share),
+                );
+            }
+        }
+    }
+
+    OwnershipStatistics::from_tracking(total_lines, credit_mode, owners)
+}
+
+/// Runs blame on every non-binary file in the tree of the given commit.
+pub fn blame_files<'context>(
+    context: &'context Context,
+    commit: &Commit,
+) -> Result<Vec<FileOwnership<'context>>> {
     let people_db = context.people_db();
     let repo = context.repo();
+    let credit_co_authors = !context.credit_mode().is_primary();
 
-    let mut owners: CombinedTracking<OwnershipScore> = CombinedTracking::new();
+    let mut files = Vec::new();
+    let mut co_authors_cache: HashMap<Oid, Vec<&Person>> = HashMap::new();
 
     let mut blame_options = BlameOptions::default();
     blame_options.newest_commit(commit.id());
@@ -130,10 +270,34 @@ pub fn calculate<'context>(
         progress.set_message(&format!("Blaming {}", entry.path().display()));
         if entry.is_file() && !entry.blob(repo).unwrap().is_binary() {
             let blame = repo.blame_file(entry.path(), Some(&mut blame_options))?;
+            let mut hunks = Vec::new();
             for hunk in blame.iter() {
-                let person = people_db.find_by_signature(hunk.orig_signature())?;
-                owners.track_person(person, |score| score.add_hunk(&hunk));
+                // The person, co-authors and day all come from the same commit, so they always
+                // agree with each other.
+                let commit_id = hunk.orig_commit_id();
+                let co_authors = if credit_co_authors {
+                    if !co_authors_cache.contains_key(&commit_id) {
+                        let commit = repo.find_commit(commit_id)?;
+                        let co_authors = coauthors::co_authors(people_db, &commit);
+                        co_authors_cache.insert(commit_id, co_authors);
+                    }
+                    co_authors_cache[&commit_id].clone()
+                } else {
+                    Vec::new()
+                };
+                hunks.push(OwnedHunk {
+                    person: people_db.find_by_signature(hunk.orig_signature())?,
+                    lines: hunk.lines_in_hunk() as u32,
+                    start_line: hunk.final_start_line(),
+                    commit: commit_id,
+                    day: dates::local_day(&hunk.orig_signature().when()),
+                    co_authors: co_authors,
+                });
             }
+            files.push(FileOwnership {
+                path: entry.path().to_path_buf(),
+                hunks: hunks,
+            });
         }
         progress.inc(1);
     }
@@ -141,5 +305,5 @@ pub fn calculate<'context>(
     progress.set_message("");
     progress.finish_and_clear();
 
-    Ok(OwnershipStatistics::from_tracking(owners))
+    Ok(files)
 }
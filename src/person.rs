@@ -6,6 +6,7 @@ use std::cmp::{PartialEq, Eq, Ord, Ordering};
 use git2::Signature;
 
 use super::errors::*;
+use dates;
 
 #[derive(Debug, PartialEq, Hash, Clone, Deserialize, Serialize)]
 pub struct Email(String);
@@ -56,12 +57,47 @@ impl<'a> From<&'a Email> for String {
     }
 }
 
+/// A team membership as written in the config file; either just the name of the team, or a name
+/// together with a weight that decides how much of the person's contributions the team gets
+/// credit for.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TeamMembership {
+    Name(String),
+    Weighted { name: String, weight: f64 },
+}
+
+impl TeamMembership {
+    pub fn name(&self) -> &str {
+        match *self {
+            TeamMembership::Name(ref name) |
+            TeamMembership::Weighted { ref name, .. } => name,
+        }
+    }
+
+    pub fn weight(&self) -> f64 {
+        match *self {
+            TeamMembership::Name(_) => 1.0,
+            TeamMembership::Weighted { weight, .. } => weight,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Person {
     name: String,
     emails: HashSet<Email>,
     #[serde(rename = "team")]
     team_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    teams: Vec<TeamMembership>,
+    /// The day this person was added to the config, when they were added by an update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    joined: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    left: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    handle: Option<String>,
 }
 
 impl PartialEq<Email> for Person {
@@ -105,6 +141,10 @@ impl Person {
             name: name.into(),
             emails: HashSet::new(),
             team_name: None,
+            teams: Vec::new(),
+            joined: None,
+            left: None,
+            handle: None,
         }
     }
 
@@ -115,6 +155,69 @@ impl Person {
         self.team_name = name.into();
     }
 
+    pub fn add_team<S>(&mut self, name: S, weight: Option<f64>)
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        self.teams.push(match weight {
+            Some(weight) => TeamMembership::Weighted {
+                name: name,
+                weight: weight,
+            },
+            None => TeamMembership::Name(name),
+        });
+    }
+
+    pub fn set_joined<S>(&mut self, date: S)
+    where
+        S: Into<Option<String>>,
+    {
+        self.joined = date.into();
+    }
+
+    pub fn joined(&self) -> Option<&str> {
+        self.joined.as_ref().map(String::as_ref)
+    }
+
+    /// The day this person was added to the config, if it is known.
+    pub fn joined_day(&self) -> Result<Option<i64>> {
+        parse_optional_day(&self.joined)
+    }
+
+    pub fn set_left<S>(&mut self, date: S)
+    where
+        S: Into<Option<String>>,
+    {
+        self.left = date.into();
+    }
+
+    pub fn left(&self) -> Option<&str> {
+        self.left.as_ref().map(String::as_ref)
+    }
+
+    /// The day this person left, if they have.
+    pub fn left_day(&self) -> Result<Option<i64>> {
+        parse_optional_day(&self.left)
+    }
+
+    /// Returns true if this person had left at the given day.
+    pub fn has_left(&self, day: i64) -> Result<bool> {
+        Ok(self.left_day()?.map_or(false, |left_day| left_day <= day))
+    }
+
+    pub fn set_handle<S>(&mut self, handle: S)
+    where
+        S: Into<Option<String>>,
+    {
+        self.handle = handle.into();
+    }
+
+    /// The username of this person on the code hosting service, like "@jane".
+    pub fn handle(&self) -> Option<&str> {
+        self.handle.as_ref().map(String::as_ref)
+    }
+
     pub fn add_email<E>(&mut self, email: E) -> bool
     where
         E: Into<Email>,
@@ -130,6 +233,33 @@ impl Person {
         self.team_name.as_ref().map(String::as_ref)
     }
 
+    /// Returns every team this person is a member of, together with the share of the person's
+    /// contributions that should be credited to that team. The shares always add up to 1.0, unless
+    /// the person is not in any team at all; then the list is empty.
+    pub fn team_memberships(&self) -> Vec<(&str, f64)> {
+        let mut memberships: Vec<(&str, f64)> = Vec::with_capacity(self.teams.len() + 1);
+
+        if let Some(ref name) = self.team_name {
+            memberships.push((name.as_str(), 1.0));
+        }
+
+        for membership in &self.teams {
+            memberships.push((membership.name(), membership.weight().max(0.0)));
+        }
+
+        let total: f64 = memberships.iter().map(|&(_, weight)| weight).sum();
+        let count = memberships.len() as f64;
+        for membership in &mut memberships {
+            membership.1 = if total > 0.0 {
+                membership.1 / total
+            } else {
+                1.0 / count
+            };
+        }
+
+        memberships
+    }
+
     pub fn emails(&self) -> &HashSet<Email> {
         &self.emails
     }
@@ -139,6 +269,18 @@ impl Person {
     }
 }
 
+fn parse_optional_day(date: &Option<String>) -> Result<Option<i64>> {
+    match *date {
+        Some(ref date) => {
+            match dates::parse_day(date) {
+                Some(day) => Ok(Some(day)),
+                None => bail!(ErrorKind::InvalidDate(date.clone())),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PeopleDatabase {
     people: Vec<Person>,
@@ -154,6 +296,10 @@ impl PeopleDatabase {
         self.people.len()
     }
 
+    pub fn iter(&self) -> ::std::slice::Iter<Person> {
+        self.people.iter()
+    }
+
     pub fn add_person(&mut self, person: Person) -> Result<()> {
         // This whole method turns out the be very ugly due to Rusts borrowchecker not being too
         // clever yet. (Non-lexical lifetimes, etc.)
@@ -232,6 +378,10 @@ where
     }
 }
 
+/// What tables and charts call the group of people that aren't in any team. JSON and YAML keep
+/// "(No team)" as the key, which consumers of that output already rely on.
+pub static NO_TEAM_NAME: &'static str = "(Others)";
+
 #[derive(Debug, Default)]
 pub struct TeamTracking<'people, T>
 where
@@ -245,10 +395,19 @@ impl<'people, T> TeamTracking<'people, T>
 where
     T: Default,
 {
-    pub fn for_person(&mut self, person: &'people Person) -> &mut T {
-        match person.team_name() {
-            Some(name) => self.for_team_name(name),
-            None => self.for_no_team(),
+    /// Calls `func` once for every team the person is a member of, passing along the weight of
+    /// that membership. People without any team are tracked as "no team" with full weight.
+    pub fn for_person<F>(&mut self, person: &'people Person, mut func: F)
+    where
+        F: FnMut(&mut T, f64),
+    {
+        let memberships = person.team_memberships();
+        if memberships.is_empty() {
+            func(self.for_no_team(), 1.0);
+        } else {
+            for (name, weight) in memberships {
+                func(self.for_team_name(name), weight);
+            }
         }
     }
 
@@ -314,12 +473,25 @@ where
         CombinedTracking::default()
     }
 
+    /// Gives the person and every team they are in the full contribution. Only meant for scores
+    /// that don't add up, like the days someone was active on; use `track_person_weighted` for
+    /// counts, or team totals end up larger than the repository total.
     pub fn track_person<F>(&mut self, person: &'people Person, mut func: F)
     where
         F: FnMut(&mut T),
     {
         func(self.people_tracking.for_person(person));
-        func(self.team_tracking.for_person(person));
+        self.team_tracking.for_person(person, |value, _| func(value));
+    }
+
+    /// Like `track_person`, but also passes the share of the contribution that should be credited.
+    /// People always get full credit, while teams get the weight of the person's membership.
+    pub fn track_person_weighted<F>(&mut self, person: &'people Person, mut func: F)
+    where
+        F: FnMut(&mut T, f64),
+    {
+        func(self.people_tracking.for_person(person), 1.0);
+        self.team_tracking.for_person(person, |value, weight| func(value, weight));
     }
 
     pub fn people_tracking(&self) -> &PeopleTracking<'people, T> {
@@ -366,6 +538,22 @@ mod tests {
         assert_eq!(person.emails().len(), 2);
     }
 
+    #[test]
+    fn it_knows_when_people_have_left() {
+        let mut person = Person::new("Jane Doe");
+        assert_eq!(person.has_left(19_783).unwrap(), false);
+
+        person.set_left(String::from("2024-03-01"));
+        assert_eq!(person.has_left(19_782).unwrap(), false);
+        assert_eq!(person.has_left(19_783).unwrap(), true);
+
+        person.set_left(String::from("last spring"));
+        assert_eq!(
+            person.has_left(19_783).unwrap_err().to_string(),
+            "Invalid date: \"last spring\"\nPlease use the YYYY-MM-DD format."
+        );
+    }
+
     #[test]
     fn it_finds_by_email_in_people_database() {
         let mut joe = Person::new("John Doe");
@@ -482,14 +670,66 @@ mod tests {
 
         let mut team_tracking: TeamTracking<Stub> = TeamTracking::default();
 
-        team_tracking.for_person(&joe).incr();
-        team_tracking.for_person(&jane).incr();
+        team_tracking.for_person(&joe, |stub, _| stub.incr());
+        team_tracking.for_person(&jane, |stub, _| stub.incr());
 
-        assert_eq!(team_tracking.for_person(&joe).current(), 1);
-        assert_eq!(team_tracking.for_person(&jane).current(), 1);
+        assert_eq!(team_tracking.for_team_name("Team 1").current(), 1);
+        assert_eq!(team_tracking.for_no_team().current(), 1);
         assert_eq!(team_tracking.no_team_value.current(), 1);
     }
 
+    #[test]
+    fn it_splits_team_memberships_by_weight() {
+        let mut joe = Person::new("John Doe");
+        joe.add_team("Team 1", Some(3.0));
+        joe.add_team("Team 2", Some(1.0));
+        assert_eq!(
+            joe.team_memberships(),
+            vec![("Team 1", 0.75), ("Team 2", 0.25)]
+        );
+
+        let mut jane = Person::new("Jane Doe");
+        jane.set_team_name(String::from("Team 1"));
+        jane.add_team("Team 2", None);
+        assert_eq!(
+            jane.team_memberships(),
+            vec![("Team 1", 0.5), ("Team 2", 0.5)]
+        );
+
+        let nobody = Person::new("Nobody");
+        assert!(nobody.team_memberships().is_empty());
+    }
+
+    #[test]
+    fn it_distributes_weighted_tracking_over_teams() {
+        #[derive(PartialEq, Debug, Default)]
+        struct Stub {
+            total: f64,
+        };
+
+        let mut joe = Person::new("John Doe");
+        joe.add_team("Team 1", Some(3.0));
+        joe.add_team("Team 2", Some(1.0));
+        let joe = joe;
+
+        let mut tracking: CombinedTracking<Stub> = CombinedTracking::new();
+        tracking.track_person_weighted(&joe, |e, weight| e.total += 100.0 * weight);
+
+        assert_eq!(
+            tracking.people_tracking.lookup.get(&joe),
+            Some(&Stub { total: 100.0 })
+        );
+        assert_eq!(
+            tracking.team_tracking.lookup.get("Team 1"),
+            Some(&Stub { total: 75.0 })
+        );
+        assert_eq!(
+            tracking.team_tracking.lookup.get("Team 2"),
+            Some(&Stub { total: 25.0 })
+        );
+        assert_eq!(tracking.team_tracking.no_team_value, Stub { total: 0.0 });
+    }
+
     #[test]
     fn it_tracks_combined_teams_and_people() {
         #[derive(PartialEq, Eq, Debug, Default)]
@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use git2::Commit;
+
+use super::errors::*;
+use super::Context;
+use coauthors;
+use count;
+use history::{self, CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+#[derive(Debug)]
+pub struct ReleaseNotes<'context> {
+    pub from: String,
+    pub to: String,
+    pub commits: u32,
+    /// People whose first commit is part of the release.
+    pub first_time_contributors: HashSet<&'context Person>,
+    pub combined_tracking: CombinedTracking<'context, ReleaseScore>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComputedContribution {
+    /// Commits credited, which is fractional when credit for co-authored commits is split.
+    #[serde(serialize_with = "count::serialize")]
+    pub commits: f64,
+    pub contributors: usize,
+    pub first_time_contributors: usize,
+}
+
+impl<'context> ReleaseNotes<'context> {
+    pub fn is_first_time(&self, person: &Person) -> bool {
+        self.first_time_contributors.contains(person)
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedContribution)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, score)| (*person, score.compute()))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1
+                .commits
+                .partial_cmp(&a.1.commits)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.name().cmp(b.0.name()))
+        });
+        toplist
+    }
+
+    /// Teams involved in the release; teams without any commits are left out.
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedContribution)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, score)| (team_name, score.compute()))
+            .filter(|&(_, ref contribution)| contribution.contributors > 0)
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.commits.partial_cmp(&a.1.commits).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReleaseScore {
+    commits: f64,
+    contributors: HashSet<String>,
+    first_time_contributors: HashSet<String>,
+}
+
+impl ReleaseScore {
+    fn add_commit(&mut self, person: &Person, share: f64, first_time: bool) {
+        self.commits += share;
+        self.contributors.insert(person.name().to_owned());
+        if first_time {
+            self.first_time_contributors.insert(person.name().to_owned());
+        }
+    }
+
+    fn compute(&self) -> ComputedContribution {
+        ComputedContribution {
+            commits: self.commits,
+            contributors: self.contributors.len(),
+            first_time_contributors: self.first_time_contributors.len(),
+        }
+    }
+}
+
+/// Everyone credited for a commit, with their share of it.
+fn contributors<'context>(
+    context: &'context Context,
+    commit: &Commit,
+) -> Result<Vec<(&'context Person, f64)>> {
+    let people_db = context.people_db();
+    let credit_mode = context.credit_mode();
+
+    let person = people_db.find_by_signature(commit.author())?;
+    let co_authors = if credit_mode.is_primary() {
+        Vec::new()
+    } else {
+        coauthors::co_authors(people_db, commit)
+    };
+    Ok(coauthors::credit(person, &co_authors, credit_mode))
+}
+
+/// Summarizes who contributed the commits that are part of `to`, but not of `from`.
+pub fn calculate<'context>(
+    context: &'context Context,
+    from: &str,
+    to: &str,
+) -> Result<ReleaseNotes<'context>> {
+    let repo = context.repo();
+    for revision in &[from, to] {
+        history::resolve_commit(repo, revision).chain_err(|| {
+            ErrorKind::InvalidRevision(revision.to_string())
+        })?;
+    }
+
+    let earlier = Selection {
+        revisions: vec![from.to_owned()],
+        ..Selection::default()
+    };
+    let mut earlier_contributors = HashSet::new();
+    for commit in CommitWalker::new(repo, &earlier)? {
+        for (person, _) in contributors(context, &commit)? {
+            earlier_contributors.insert(person);
+        }
+    }
+
+    let release = Selection {
+        revisions: vec![to.to_owned(), format!("^{}", from)],
+        ..Selection::default()
+    };
+    let mut commits = 0;
+    let mut first_time_contributors = HashSet::new();
+    let mut tracking: CombinedTracking<ReleaseScore> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(repo, &release)? {
+        commits += 1;
+        for (person, share) in contributors(context, &commit)? {
+            let first_time = !earlier_contributors.contains(person);
+            if first_time {
+                first_time_contributors.insert(person);
+            }
+            tracking.track_person_weighted(person, |score, weight| {
+                score.add_commit(person, share * weight, first_time)
+            });
+        }
+    }
+
+    Ok(ReleaseNotes {
+        from: from.to_owned(),
+        to: to.to_owned(),
+        commits: commits,
+        first_time_contributors: first_time_contributors,
+        combined_tracking: tracking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_counts_contributors_once() {
+        let alice = Person::new("Alice");
+        let bob = Person::new("Bob");
+
+        let mut score = ReleaseScore::default();
+        score.add_commit(&alice, 1.0, false);
+        score.add_commit(&bob, 0.5, true);
+        score.add_commit(&bob, 1.0, true);
+
+        let computed = score.compute();
+        assert_eq!(computed.commits, 2.5);
+        assert_eq!(computed.contributors, 2);
+        assert_eq!(computed.first_time_contributors, 1);
+    }
+}
@@ -0,0 +1,35 @@
+use super::errors::*;
+use super::Context;
+use activity::{self, ActivityStatistics};
+use commits::{self, CommitStatistics};
+use dates;
+use history::Selection;
+use ownership::{self, OwnershipStatistics};
+use timeline::{self, Timeline};
+
+/// The reports that make up the published overview of a repository.
+#[derive(Debug)]
+pub struct Report<'context> {
+    pub head_sha: String,
+    pub generated_on: String,
+    pub ownership: OwnershipStatistics<'context>,
+    pub commits: CommitStatistics<'context>,
+    pub activity: ActivityStatistics<'context>,
+    pub timeline: Timeline<'context>,
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<Report<'context>> {
+    let head_commit = context.head_commit()?;
+
+    Ok(Report {
+        head_sha: head_commit.id().to_string(),
+        generated_on: dates::format_day(dates::today()),
+        ownership: ownership::calculate(context, &head_commit, selection)?,
+        commits: commits::calculate(context, selection)?,
+        activity: activity::calculate(context, selection)?,
+        timeline: timeline::calculate(context, selection)?,
+    })
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeSet;
+
+use git2::Commit;
+
+use super::errors::*;
+use super::Context;
+use dates;
+use history::{CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+/// A range of whole days, such as a streak of commits or a gap between them.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Span {
+    pub days: i64,
+    pub from: String,
+    pub to: String,
+}
+
+impl Span {
+    fn new(first_day: i64, last_day: i64) -> Span {
+        Span {
+            days: last_day - first_day + 1,
+            from: dates::format_day(first_day),
+            to: dates::format_day(last_day),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ComputedStreaks {
+    pub longest_streak: Option<Span>,
+    pub current_streak: i64,
+    pub longest_gap: Option<Span>,
+}
+
+#[derive(Debug, Default)]
+pub struct ActiveDays {
+    days: BTreeSet<i64>,
+}
+
+impl ActiveDays {
+    fn add_commit(&mut self, commit: &Commit) {
+        self.days.insert(dates::local_day(&commit.author().when()));
+    }
+
+    /// Computes streaks and gaps. A streak is only current if it includes today or yesterday, so
+    /// that nobody loses their streak just because they have not committed anything yet today.
+    pub fn compute(&self, today: i64) -> ComputedStreaks {
+        let mut longest_streak: Option<(i64, i64)> = None;
+        let mut longest_gap: Option<(i64, i64)> = None;
+        let mut current: Option<(i64, i64)> = None;
+
+        for &day in &self.days {
+            current = match current {
+                Some((first, last)) if day == last + 1 => Some((first, day)),
+                Some((_, last)) => {
+                    if longest_gap.map_or(true, |(from, to)| day - last - 1 > to - from + 1) {
+                        longest_gap = Some((last + 1, day - 1));
+                    }
+                    Some((day, day))
+                }
+                None => Some((day, day)),
+            };
+
+            if let Some((first, last)) = current {
+                if longest_streak.map_or(true, |(from, to)| last - first > to - from) {
+                    longest_streak = Some((first, last));
+                }
+            }
+        }
+
+        let current_streak = match current {
+            Some((first, last)) if last >= today - 1 => last - first + 1,
+            _ => 0,
+        };
+
+        ComputedStreaks {
+            longest_streak: longest_streak.map(|(first, last)| Span::new(first, last)),
+            current_streak: current_streak,
+            longest_gap: longest_gap.map(|(first, last)| Span::new(first, last)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StreakStatistics<'context> {
+    pub today: i64,
+    pub repository: ActiveDays,
+    pub combined_tracking: CombinedTracking<'context, ActiveDays>,
+}
+
+impl<'context> StreakStatistics<'context> {
+    pub fn repository_streaks(&self) -> ComputedStreaks {
+        self.repository.compute(self.today)
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, ComputedStreaks)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, days)| (*person, days.compute(self.today)))
+            .collect();
+        toplist.sort_by(|a, b| streak_length(&b.1).cmp(&streak_length(&a.1))); // Note: Reverse sort
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, ComputedStreaks)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .team_iter()
+            .map(|(team_name, days)| (team_name, days.compute(self.today)))
+            .collect();
+        toplist.sort_by(|a, b| streak_length(&b.1).cmp(&streak_length(&a.1))); // Note: Reverse sort
+        toplist
+    }
+
+    /// The person with the longest streak ever, and that streak.
+    pub fn longest_streak_by_anyone(&self) -> Option<(&Person, Span)> {
+        self.people_toplist()
+            .into_iter()
+            .filter_map(|(person, streaks)| {
+                streaks.longest_streak.map(|streak| (person, streak))
+            })
+            .next()
+    }
+
+    /// The person with the longest streak that is still going on, and its length.
+    pub fn longest_current_streak(&self) -> Option<(&Person, i64)> {
+        self.people_toplist()
+            .into_iter()
+            .map(|(person, streaks)| (person, streaks.current_streak))
+            .filter(|&(_, days)| days > 0)
+            .max_by_key(|&(_, days)| days)
+    }
+}
+
+fn streak_length(streaks: &ComputedStreaks) -> i64 {
+    streaks.longest_streak.as_ref().map_or(0, |streak| streak.days)
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<StreakStatistics<'context>> {
+    let people_db = context.people_db();
+
+    let mut repository = ActiveDays::default();
+    let mut tracking: CombinedTracking<ActiveDays> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        let person = people_db.find_by_signature(commit.author())?;
+        repository.add_commit(&commit);
+        // A team is active on every day any of its members is, so teams get all days in full.
+        tracking.track_person(person, |days| days.add_commit(&commit));
+    }
+
+    Ok(StreakStatistics {
+        // Current streaks are counted up to the end of the selection.
+        today: selection.until.map_or(dates::today(), |until| until.min(dates::today())),
+        repository: repository,
+        combined_tracking: tracking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_days(days: &[i64]) -> ActiveDays {
+        ActiveDays { days: days.iter().cloned().collect() }
+    }
+
+    #[test]
+    fn it_finds_longest_streak_and_gap() {
+        let streaks = active_days(&[1, 2, 3, 10, 11, 12, 13, 15]).compute(100);
+
+        assert_eq!(streaks.longest_streak, Some(Span::new(10, 13)));
+        assert_eq!(streaks.longest_gap, Some(Span::new(4, 9)));
+        assert_eq!(streaks.current_streak, 0);
+    }
+
+    #[test]
+    fn it_counts_current_streak_until_yesterday() {
+        assert_eq!(active_days(&[5, 8, 9]).compute(9).current_streak, 2);
+        assert_eq!(active_days(&[5, 8, 9]).compute(10).current_streak, 2);
+        assert_eq!(active_days(&[5, 8, 9]).compute(11).current_streak, 0);
+    }
+
+    #[test]
+    fn it_handles_no_activity() {
+        let streaks = active_days(&[]).compute(10);
+
+        assert_eq!(streaks.longest_streak, None);
+        assert_eq!(streaks.longest_gap, None);
+        assert_eq!(streaks.current_streak, 0);
+    }
+}
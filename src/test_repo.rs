@@ -0,0 +1,79 @@
+//! Throwaway repositories for tests that need real commits.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use git2::{Oid, Repository, Signature, Time};
+
+use context::Context;
+use person::{PeopleDatabase, Person};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A repository in a temporary directory, which is removed again when the value is dropped.
+pub struct TestRepo {
+    pub repo: Repository,
+    path: PathBuf,
+    commits: i64,
+}
+
+impl TestRepo {
+    pub fn new() -> TestRepo {
+        let path = env::temp_dir().join(format!(
+            "git-trivia-test-{}-{}",
+            process::id(),
+            NEXT_ID.fetch_add(1, Ordering::SeqCst)
+        ));
+        let repo = Repository::init(&path).unwrap();
+        TestRepo {
+            repo: repo,
+            path: path,
+            commits: 0,
+        }
+    }
+
+    /// Commits a tree holding exactly the given files, without moving any branch. Every commit
+    /// is made a day after the previous one.
+    pub fn commit(&mut self, parents: &[Oid], files: &[(&str, &str)], message: &str) -> Oid {
+        let mut builder = self.repo.treebuilder(None).unwrap();
+        for &(name, content) in files {
+            let blob = self.repo.blob(content.as_bytes()).unwrap();
+            builder.insert(name, blob, 0o100644).unwrap();
+        }
+        let tree = self.repo.find_tree(builder.write().unwrap()).unwrap();
+
+        self.commits += 1;
+        let time = Time::new(1_500_000_000 + self.commits * 86_400, 0);
+        let signature = Signature::new("Alice", "alice@example.com", &time).unwrap();
+
+        let parents: Vec<_> = parents.iter().map(|&oid| self.repo.find_commit(oid).unwrap()).collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+        self.repo
+            .commit(None, &signature, &signature, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    /// Opens the repository as a context in which Alice, the author of every commit, is known.
+    pub fn context(&self) -> Context {
+        let mut alice = Person::new("Alice");
+        alice.add_email("alice@example.com");
+        let mut people_db = PeopleDatabase::new();
+        people_db.add_person(alice).unwrap();
+
+        Context::new(Repository::open(&self.path).unwrap(), people_db)
+    }
+
+    /// Points a branch or tag at the commit. `HEAD` follows `refs/heads/master`.
+    pub fn set_ref(&self, name: &str, oid: Oid) {
+        self.repo.reference(name, oid, true, "test").unwrap();
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
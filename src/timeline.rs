@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::errors::*;
+use super::Context;
+use coauthors;
+use dates;
+use history::{CommitWalker, Selection};
+use person::{Person, CombinedTracking};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Month {
+    pub year: i64,
+    pub month: u32,
+}
+
+impl Month {
+    pub fn from_day(day: i64) -> Month {
+        let (year, month, _) = dates::civil_from_days(day);
+        Month {
+            year: year,
+            month: month,
+        }
+    }
+
+    pub fn next(&self) -> Month {
+        if self.month == 12 {
+            Month {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Month {
+                year: self.year,
+                month: self.month + 1,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Commits per calendar month, in the timezone of each commit's author.
+#[derive(Debug, Default)]
+pub struct MonthlyCommits {
+    months: BTreeMap<Month, f64>,
+}
+
+impl MonthlyCommits {
+    fn add_commit(&mut self, day: i64, share: f64) {
+        *self.months.entry(Month::from_day(day)).or_insert(0.0) += share;
+    }
+
+    pub fn get(&self, month: Month) -> f64 {
+        self.months.get(&month).cloned().unwrap_or(0.0)
+    }
+
+    pub fn total(&self) -> f64 {
+        self.months.values().fold(0.0, |total, commits| total + commits)
+    }
+
+    /// Months with at least one commit.
+    pub fn active_months(&self) -> usize {
+        self.months.len()
+    }
+
+    /// The month with the most commits; the earliest one on ties.
+    pub fn busiest_month(&self) -> Option<(Month, f64)> {
+        self.months.iter().fold(None, |best, (month, commits)| match best {
+            Some((_, best_commits)) if best_commits >= *commits => best,
+            _ => Some((*month, *commits)),
+        })
+    }
+
+    /// Commits for each of the given months, zero for months without any.
+    pub fn series(&self, months: &[Month]) -> Vec<f64> {
+        months.iter().map(|month| self.get(*month)).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Timeline<'context> {
+    pub repository: MonthlyCommits,
+    pub combined_tracking: CombinedTracking<'context, MonthlyCommits>,
+}
+
+impl<'context> Timeline<'context> {
+    /// Every month from the first to the last commit, including quiet months in between.
+    pub fn months(&self) -> Vec<Month> {
+        let mut months = Vec::new();
+        let first = self.repository.months.keys().next();
+        let last = self.repository.months.keys().next_back();
+        if let (Some(first), Some(last)) = (first, last) {
+            let mut month = *first;
+            while month <= *last {
+                months.push(month);
+                month = month.next();
+            }
+        }
+        months
+    }
+
+    pub fn people_toplist(&self) -> Vec<(&Person, &MonthlyCommits)> {
+        let mut toplist: Vec<_> = self.combined_tracking
+            .people_iter()
+            .map(|(person, commits)| (*person, commits))
+            .collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.total().partial_cmp(&a.1.total()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+
+    pub fn teams_toplist(&self) -> Vec<(Option<&str>, &MonthlyCommits)> {
+        let mut toplist: Vec<_> = self.combined_tracking.team_iter().collect();
+        // Note: Reverse sort
+        toplist.sort_by(|a, b| {
+            b.1.total().partial_cmp(&a.1.total()).unwrap_or(Ordering::Equal)
+        });
+        toplist
+    }
+}
+
+pub fn calculate<'context>(
+    context: &'context Context,
+    selection: &Selection,
+) -> Result<Timeline<'context>> {
+    let people_db = context.people_db();
+    let credit_mode = context.credit_mode();
+
+    let mut repository = MonthlyCommits::default();
+    let mut tracking: CombinedTracking<MonthlyCommits> = CombinedTracking::new();
+
+    for commit in CommitWalker::new(context.repo(), selection)? {
+        let day = dates::local_day(&commit.author().when());
+        let person = people_db.find_by_signature(commit.author())?;
+        let co_authors = if credit_mode.is_primary() {
+            Vec::new()
+        } else {
+            coauthors::co_authors(people_db, &commit)
+        };
+
+        repository.add_commit(day, 1.0);
+        for (contributor, share) in coauthors::credit(person, &co_authors, credit_mode) {
+            tracking.track_person_weighted(
+                contributor,
+                |commits, weight| commits.add_commit(day, share * weight),
+            );
+        }
+    }
+
+    Ok(Timeline {
+        repository: repository,
+        combined_tracking: tracking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_fills_in_quiet_months() {
+        let mut repository = MonthlyCommits::default();
+        repository.add_commit(dates::days_from_civil(2017, 11, 30), 1.0);
+        repository.add_commit(dates::days_from_civil(2018, 2, 1), 1.0);
+        repository.add_commit(dates::days_from_civil(2018, 2, 28), 0.5);
+
+        let timeline = Timeline {
+            repository: repository,
+            combined_tracking: CombinedTracking::new(),
+        };
+
+        let months: Vec<String> = timeline.months().iter().map(|m| m.to_string()).collect();
+        assert_eq!(months, vec!["2017-11", "2017-12", "2018-01", "2018-02"]);
+        assert_eq!(timeline.repository.series(&timeline.months()), vec![1.0, 0.0, 0.0, 1.5]);
+        assert_eq!(timeline.repository.active_months(), 2);
+        assert_eq!(
+            timeline.repository.busiest_month(),
+            Some((Month { year: 2018, month: 2 }, 1.5))
+        );
+    }
+}
@@ -116,3 +116,40 @@ impl<'repo> Iterator for TreeWalker<'repo> {
         }
     }
 }
+
+/// Returns the directory a path lives in, cut off at `depth` levels. Files directly in the root of
+/// the repository are put in ".".
+pub fn directory_at_depth(path: &Path, depth: usize) -> String {
+    let components: Vec<_> = match path.parent() {
+        Some(parent) => {
+            parent
+                .components()
+                .take(depth)
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    if components.is_empty() {
+        String::from(".")
+    } else {
+        components.join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_groups_files_by_directory() {
+        assert_eq!(directory_at_depth(Path::new("README.md"), 1), ".");
+        assert_eq!(directory_at_depth(Path::new("src/main.rs"), 1), "src");
+        assert_eq!(directory_at_depth(Path::new("src/formatters/json.rs"), 1), "src");
+        assert_eq!(
+            directory_at_depth(Path::new("src/formatters/json.rs"), 2),
+            "src/formatters"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+
+use ownership::FileOwnership;
+use tree_walker::directory_at_depth;
+use person::Person;
+
+/// The result of a truck factor calculation: how many owners need to leave before more than half
+/// of the files are left without a significant owner, and who those owners are.
+#[derive(Debug)]
+pub struct TruckFactor<K> {
+    pub files: usize,
+    pub critical: Vec<K>,
+}
+
+impl<K> TruckFactor<K> {
+    pub fn factor(&self) -> usize {
+        self.critical.len()
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectoryTruckFactor<'context> {
+    pub path: String,
+    pub people: TruckFactor<&'context Person>,
+    pub teams: TruckFactor<Option<&'context str>>,
+}
+
+#[derive(Debug)]
+pub struct TruckFactorReport<'context> {
+    pub owner_threshold: f64,
+    pub people: TruckFactor<&'context Person>,
+    pub teams: TruckFactor<Option<&'context str>>,
+    pub directories: Vec<DirectoryTruckFactor<'context>>,
+}
+
+/// Calculates the truck factor of the repository, and of each directory at the given depth.
+///
+/// An owner is significant for a file if they own at least `owner_threshold` of its lines. The
+/// biggest owner of a file is always significant, even if nobody reaches the threshold.
+pub fn calculate<'context>(
+    files: &[FileOwnership<'context>],
+    owner_threshold: f64,
+    depth: usize,
+) -> TruckFactorReport<'context> {
+    let files: Vec<&FileOwnership> = files.iter().filter(|file| file.total_lines() > 0).collect();
+
+    let mut directories: BTreeMap<String, Vec<&FileOwnership>> = BTreeMap::new();
+    for file in &files {
+        directories
+            .entry(directory_at_depth(&file.path, depth))
+            .or_insert_with(Vec::new)
+            .push(*file);
+    }
+
+    TruckFactorReport {
+        owner_threshold: owner_threshold,
+        people: people_truck_factor(&files, owner_threshold),
+        teams: teams_truck_factor(&files, owner_threshold),
+        directories: directories
+            .into_iter()
+            .map(|(path, files)| {
+                DirectoryTruckFactor {
+                    people: people_truck_factor(&files, owner_threshold),
+                    teams: teams_truck_factor(&files, owner_threshold),
+                    path: path,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn people_truck_factor<'context>(
+    files: &[&FileOwnership<'context>],
+    owner_threshold: f64,
+) -> TruckFactor<&'context Person> {
+    let owners: Vec<HashSet<&Person>> = files
+        .iter()
+        .map(|file| {
+            let lines = file.lines_per_person()
+                .into_iter()
+                .map(|(person, lines)| (person, lines as f64))
+                .collect();
+            significant_owners(lines, owner_threshold)
+        })
+        .collect();
+    truck_factor(owners)
+}
+
+fn teams_truck_factor<'context>(
+    files: &[&FileOwnership<'context>],
+    owner_threshold: f64,
+) -> TruckFactor<Option<&'context str>> {
+    let owners: Vec<HashSet<Option<&str>>> = files
+        .iter()
+        .map(|file| significant_owners(file.lines_per_team(), owner_threshold))
+        .collect();
+    truck_factor(owners)
+}
+
+fn significant_owners<K>(lines: HashMap<K, f64>, owner_threshold: f64) -> HashSet<K>
+where
+    K: Hash + Eq + Ord + Clone,
+{
+    let total: f64 = lines.values().sum();
+    let mut owners: HashSet<K> = lines
+        .iter()
+        .filter(|&(_, &owned)| total > 0.0 && owned / total >= owner_threshold)
+        .map(|(owner, _)| owner.clone())
+        .collect();
+
+    if owners.is_empty() {
+        let biggest = lines.into_iter().fold(None, |best: Option<(K, f64)>, (owner, owned)| {
+            match best {
+                Some((best_owner, best_owned)) => {
+                    if owned > best_owned || (owned == best_owned && owner < best_owner) {
+                        Some((owner, owned))
+                    } else {
+                        Some((best_owner, best_owned))
+                    }
+                }
+                None => Some((owner, owned)),
+            }
+        });
+        if let Some((owner, _)) = biggest {
+            owners.insert(owner);
+        }
+    }
+
+    owners
+}
+
+/// Greedily removes the owner that is significant for the most remaining files, until more than
+/// half of the files have no significant owner left.
+pub fn truck_factor<K>(mut files: Vec<HashSet<K>>) -> TruckFactor<K>
+where
+    K: Hash + Eq + Ord + Clone,
+{
+    files.retain(|owners| !owners.is_empty());
+    let total = files.len();
+    let mut critical = Vec::new();
+
+    while files.iter().filter(|owners| owners.is_empty()).count() * 2 <= total {
+        let most_critical = {
+            let mut coverage: BTreeMap<&K, usize> = BTreeMap::new();
+            for owner in files.iter().flat_map(|owners| owners.iter()) {
+                *coverage.entry(owner).or_insert(0) += 1;
+            }
+
+            // BTreeMap iterates in order, so ties are broken the same way every time.
+            coverage
+                .into_iter()
+                .fold(None, |best: Option<(&K, usize)>, (owner, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((owner, count)),
+                })
+                .map(|(owner, _)| owner.clone())
+        };
+
+        match most_critical {
+            Some(owner) => {
+                for owners in &mut files {
+                    owners.remove(&owner);
+                }
+                critical.push(owner);
+            }
+            None => break,
+        }
+    }
+
+    TruckFactor {
+        files: total,
+        critical: critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(names: &[&'static str]) -> HashSet<&'static str> {
+        names.iter().cloned().collect()
+    }
+
+    #[test]
+    fn it_removes_the_most_critical_owners_first() {
+        let files = vec![
+            owners(&["alice"]),
+            owners(&["alice", "bob"]),
+            owners(&["alice"]),
+            owners(&["carol"]),
+            owners(&["bob", "carol"]),
+        ];
+
+        let result = truck_factor(files);
+
+        assert_eq!(result.files, 5);
+        assert_eq!(result.factor(), 2);
+        assert_eq!(result.critical, vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn it_has_a_truck_factor_of_zero_without_files() {
+        let result: TruckFactor<&str> = truck_factor(vec![owners(&[])]);
+
+        assert_eq!(result.files, 0);
+        assert_eq!(result.factor(), 0);
+    }
+
+    #[test]
+    fn it_always_treats_the_biggest_owner_as_significant() {
+        let mut lines = HashMap::new();
+        lines.insert("alice", 10.0);
+        lines.insert("bob", 20.0);
+        lines.insert("carol", 15.0);
+
+        assert_eq!(significant_owners(lines.clone(), 0.3), owners(&["bob", "carol"]));
+        assert_eq!(significant_owners(lines, 0.9), owners(&["bob"]));
+    }
+}